@@ -66,15 +66,16 @@
           __simpalt_build_prompt() {
             (( $? != 0 )) && local has_error='-e'
             [ "''${jobstates}" ] && local has_jobs='-j'
+            ((''${precmd_functions[(Ie)_direnv_hook]})) && local -x SIMPALT_DIRENV_HOOK=1
         ''
         + (
           if toggleBinding == null then
             ''
-              simpalt l -z '${symbol}' $has_error $has_jobs
+              simpalt l -z -c$COLUMNS '${symbol}' $has_error $has_jobs
             ''
           else
             ''
-              simpalt l -z $SIMPALT_MODE '${symbol}' $has_error $has_jobs
+              simpalt l -z $SIMPALT_MODE -c$COLUMNS '${symbol}' $has_error $has_jobs
             ''
         )
         + ''
@@ -82,7 +83,7 @@
 
           __simpalt_build_r_prompt() {
             if (( COLUMNS > 120 )); then
-              simpalt r -z
+              simpalt r -z ''${=SIMPALT_RIGHT}
             fi
           }
         ''
@@ -110,8 +111,9 @@
           PROMPT='$(__simpalt_build_prompt)'
           RPROMPT='$(__simpalt_build_r_prompt)'
 
-          # Avoid penv from setting the PROMPT
+          # Avoid penv and conda from setting the PROMPT
           VIRTUAL_ENV_DISABLE_PROMPT=1
+          CONDA_CHANGEPS1=false
         '';
     };
 }
@@ -8,13 +8,9 @@ pub fn parse() -> command::Command {
     match command.as_deref() {
         Some("r") => command::Command::Right(parse_right(args)),
         Some("l") => command::Command::Left(parse_left(args)),
-        Some("t") => {
-            if let Some(tmux) = args.next().map(|pwd| command::Tmux { pwd }) {
-                command::Command::Tmux(tmux)
-            } else {
-                command::Command::Help(command::Help { bin })
-            }
-        }
+        Some("t") => parse_tmux(args).unwrap_or(command::Command::Help(command::Help { bin })),
+        Some("d" | "direnv") => command::Command::Direnv,
+        Some("j" | "json") => command::Command::Json,
         Some("v") => command::Command::Version,
         _ => command::Command::Help(command::Help { bin }),
     }
@@ -23,11 +19,25 @@ pub fn parse() -> command::Command {
 fn parse_right(args: impl Iterator<Item = String>) -> command::Right {
     let this = command::Right {
         compat: command::Compat::None,
+        segments: Vec::new(),
     };
 
     args.filter(|s| !s.is_empty()).fold(this, |mut acc, curr| {
         if curr == "-z" {
             acc.compat = command::Compat::Zsh;
+        } else if let Some(segment) = match curr.as_str() {
+            "-k" => Some(command::Segment::Kube),
+            "-d" => Some(command::Segment::Docker),
+            "-a" => Some(command::Segment::Aws),
+            "-g" => Some(command::Segment::Gcp),
+            "-m" => Some(command::Segment::Azure),
+            "-t" => Some(command::Segment::Toolchain),
+            "-b" => Some(command::Segment::Battery),
+            _ => None,
+        } {
+            if !acc.segments.contains(&segment) {
+                acc.segments.push(segment);
+            }
         } else if let Some(sub) = curr.strip_prefix("-w").filter(|s| !s.is_empty()) {
             acc.compat = command::Compat::Win(String::from(sub));
         }
@@ -35,12 +45,79 @@ fn parse_right(args: impl Iterator<Item = String>) -> command::Right {
     })
 }
 
+fn parse_tmux(mut args: impl Iterator<Item = String>) -> Option<command::Command> {
+    match args.next()?.as_str() {
+        "left" => Some(command::Command::TmuxLeft(parse_tmux_left(args))),
+        "window" => parse_tmux_window(args).map(command::Command::TmuxWindow),
+        pwd => Some(command::Command::Tmux(parse_tmux_status(
+            String::from(pwd),
+            args,
+        ))),
+    }
+}
+
+fn parse_tmux_left(args: impl Iterator<Item = String>) -> command::TmuxLeft {
+    let this = command::TmuxLeft {
+        session: None,
+        host: false,
+    };
+
+    args.filter(|s| !s.is_empty()).fold(this, |mut acc, curr| {
+        if curr == "-h" {
+            acc.host = true;
+        } else if let Some(session) = curr.strip_prefix("-s").filter(|s| !s.is_empty()) {
+            acc.session = Some(String::from(session));
+        }
+        acc
+    })
+}
+
+fn parse_tmux_window(mut args: impl Iterator<Item = String>) -> Option<command::TmuxWindow> {
+    Some(command::TmuxWindow {
+        index: args.next()?,
+        name: args.next()?,
+        active: matches!(args.next()?.as_str(), "1" | "true"),
+        path: args.next()?,
+    })
+}
+
+fn parse_tmux_status(pwd: String, args: impl Iterator<Item = String>) -> command::Tmux {
+    let this = command::Tmux {
+        pwd,
+        segments: Vec::new(),
+        session: None,
+        host: false,
+        clock: false,
+    };
+
+    args.filter(|s| !s.is_empty()).fold(this, |mut acc, curr| {
+        if curr == "-h" {
+            acc.host = true;
+        } else if curr == "-c" {
+            acc.clock = true;
+        } else if let Some(segment) = match curr.as_str() {
+            "-l" => Some(command::TmuxSegment::Load),
+            "-m" => Some(command::TmuxSegment::Memory),
+            _ => None,
+        } {
+            if !acc.segments.contains(&segment) {
+                acc.segments.push(segment);
+            }
+        } else if let Some(session) = curr.strip_prefix("-s").filter(|s| !s.is_empty()) {
+            acc.session = Some(String::from(session));
+        }
+        acc
+    })
+}
+
 fn parse_left(args: impl Iterator<Item = String>) -> command::Left {
     let this = command::Left {
         host: None,
         error: false,
         jobs: false,
         long: false,
+        columns: None,
+        percent: 50,
         compat: command::Compat::None,
     };
 
@@ -55,6 +132,15 @@ fn parse_left(args: impl Iterator<Item = String>) -> command::Left {
             acc.compat = command::Compat::Zsh;
         } else if let Some(sub) = curr.strip_prefix("-w").filter(|s| !s.is_empty()) {
             acc.compat = command::Compat::Win(String::from(sub));
+        } else if let Some(columns) = curr.strip_prefix("-c") {
+            // Malformed widths are ignored rather than taken as the host
+            if let Ok(columns) = columns.parse() {
+                acc.columns = Some(columns);
+            }
+        } else if let Some(percent) = curr.strip_prefix("-p") {
+            if let Some(percent) = percent.parse().ok().filter(|p| *p <= 100) {
+                acc.percent = percent;
+            }
         } else {
             acc.host = Some(curr);
         }
@@ -71,6 +157,7 @@ mod tests {
         assert_eq!(
             command::Right {
                 compat: command::Compat::None,
+                segments: Vec::new(),
             },
             super::parse_right(std::iter::empty())
         );
@@ -81,6 +168,7 @@ mod tests {
         assert_eq!(
             command::Right {
                 compat: command::Compat::None,
+                segments: Vec::new(),
             },
             super::parse_right(["bla", "-w", "ble"].map(String::from).into_iter())
         );
@@ -91,6 +179,7 @@ mod tests {
         assert_eq!(
             command::Right {
                 compat: command::Compat::Zsh,
+                segments: Vec::new(),
             },
             super::parse_right(["-z"].map(String::from).into_iter())
         );
@@ -101,6 +190,7 @@ mod tests {
         assert_eq!(
             command::Right {
                 compat: command::Compat::Win(String::from("2")),
+                segments: Vec::new(),
             },
             super::parse_right(["-w2"].map(String::from).into_iter())
         );
@@ -111,11 +201,122 @@ mod tests {
         assert_eq!(
             command::Right {
                 compat: command::Compat::Zsh,
+                segments: Vec::new(),
             },
             super::parse_right(["-w12", "-z"].map(String::from).into_iter())
         );
     }
 
+    #[test]
+    fn parse_right_segments() {
+        assert_eq!(
+            command::Right {
+                compat: command::Compat::Zsh,
+                segments: vec![
+                    command::Segment::Gcp,
+                    command::Segment::Kube,
+                    command::Segment::Azure,
+                    command::Segment::Aws,
+                    command::Segment::Toolchain,
+                    command::Segment::Docker,
+                    command::Segment::Battery,
+                ],
+            },
+            super::parse_right(
+                ["-g", "-k", "-z", "-m", "-g", "-a", "-t", "-d", "-b"]
+                    .map(String::from)
+                    .into_iter()
+            )
+        );
+    }
+
+    #[test]
+    fn parse_tmux_empty() {
+        assert_eq!(None, super::parse_tmux(std::iter::empty()));
+    }
+
+    #[test]
+    fn parse_tmux_pwd() {
+        assert_eq!(
+            Some(command::Command::Tmux(command::Tmux {
+                pwd: String::from("/some/path"),
+                segments: Vec::new(),
+                session: None,
+                host: false,
+                clock: false,
+            })),
+            super::parse_tmux(["/some/path"].map(String::from).into_iter())
+        );
+    }
+
+    #[test]
+    fn parse_tmux_segments() {
+        assert_eq!(
+            Some(command::Command::Tmux(command::Tmux {
+                pwd: String::from("/some/path"),
+                segments: vec![command::TmuxSegment::Memory, command::TmuxSegment::Load],
+                session: None,
+                host: false,
+                clock: false,
+            })),
+            super::parse_tmux(
+                ["/some/path", "-m", "-x", "-l", "-m"]
+                    .map(String::from)
+                    .into_iter()
+            )
+        );
+    }
+
+    #[test]
+    fn parse_tmux_status() {
+        assert_eq!(
+            Some(command::Command::Tmux(command::Tmux {
+                pwd: String::from("/some/path"),
+                segments: vec![command::TmuxSegment::Load],
+                session: Some(String::from("work")),
+                host: true,
+                clock: true,
+            })),
+            super::parse_tmux(
+                ["/some/path", "-c", "-s", "-sfirst", "-l", "-swork", "-h"]
+                    .map(String::from)
+                    .into_iter()
+            )
+        );
+    }
+
+    #[test]
+    fn parse_tmux_left() {
+        assert_eq!(
+            Some(command::Command::TmuxLeft(command::TmuxLeft {
+                session: Some(String::from("work")),
+                host: true,
+            })),
+            super::parse_tmux(["left", "-h", "-swork"].map(String::from).into_iter())
+        );
+    }
+
+    #[test]
+    fn parse_tmux_window() {
+        assert_eq!(
+            Some(command::Command::TmuxWindow(command::TmuxWindow {
+                index: String::from("1"),
+                name: String::from("zsh"),
+                active: true,
+                path: String::from("/some/path"),
+            })),
+            super::parse_tmux(
+                ["window", "1", "zsh", "1", "/some/path"]
+                    .map(String::from)
+                    .into_iter()
+            )
+        );
+        assert_eq!(
+            None,
+            super::parse_tmux(["window", "1", "zsh", "0"].map(String::from).into_iter())
+        );
+    }
+
     #[test]
     fn parse_left_empty() {
         assert_eq!(
@@ -124,6 +325,8 @@ mod tests {
                 error: false,
                 jobs: false,
                 long: false,
+                columns: None,
+                percent: 50,
                 compat: command::Compat::None,
             },
             super::parse_left(std::iter::empty())
@@ -138,6 +341,8 @@ mod tests {
                 error: false,
                 jobs: false,
                 long: false,
+                columns: None,
+                percent: 50,
                 compat: command::Compat::Win(String::from("yo")),
             },
             super::parse_left(["-wyo"].map(String::from).into_iter())
@@ -152,6 +357,8 @@ mod tests {
                 error: false,
                 jobs: false,
                 long: false,
+                columns: None,
+                percent: 50,
                 compat: command::Compat::None,
             },
             super::parse_left(["-w"].map(String::from).into_iter())
@@ -166,6 +373,8 @@ mod tests {
                 error: false,
                 jobs: false,
                 long: false,
+                columns: None,
+                percent: 50,
                 compat: command::Compat::None,
             },
             super::parse_left(["first", "second", "last"].map(String::from).into_iter())
@@ -180,15 +389,54 @@ mod tests {
                 error: true,
                 jobs: true,
                 long: true,
+                columns: Some(120),
+                percent: 30,
                 compat: command::Compat::Zsh,
             },
             super::parse_left(
                 [
-                    "first", "", "-3", "-e", "second", "-j", "last", "-l", "-e", "-j", "-z", ""
+                    "first", "", "-3", "-e", "second", "-j", "last", "-l", "-e", "-j", "-z", "",
+                    "-c120", "-p30"
                 ]
                 .map(String::from)
                 .into_iter()
             )
         );
     }
+
+    #[test]
+    fn parse_left_width() {
+        assert_eq!(
+            command::Left {
+                host: None,
+                error: false,
+                jobs: false,
+                long: false,
+                columns: Some(80),
+                percent: 40,
+                compat: command::Compat::None,
+            },
+            super::parse_left(["-c80", "-p40"].map(String::from).into_iter())
+        );
+    }
+
+    #[test]
+    fn parse_left_invalid_width() {
+        assert_eq!(
+            command::Left {
+                host: Some(String::from("host")),
+                error: false,
+                jobs: false,
+                long: false,
+                columns: None,
+                percent: 50,
+                compat: command::Compat::None,
+            },
+            super::parse_left(
+                ["host", "-c", "-cwide", "-p101", "-p"]
+                    .map(String::from)
+                    .into_iter()
+            )
+        );
+    }
 }
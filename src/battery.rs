@@ -0,0 +1,112 @@
+const DEFAULT_THRESHOLD: u8 = 20;
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub struct Battery {
+    pub capacity: u8,
+    pub discharging: bool,
+    pub low: bool,
+}
+
+pub fn status() -> Option<Battery> {
+    let threshold = std::env::var("SIMPALT_BATTERY_THRESHOLD")
+        .ok()
+        .and_then(|threshold| threshold.trim().parse().ok())
+        .unwrap_or(DEFAULT_THRESHOLD);
+
+    status_in(std::path::Path::new("/sys/class/power_supply"), threshold)
+}
+
+fn status_in(root: &std::path::Path, threshold: u8) -> Option<Battery> {
+    let mut batteries = std::fs::read_dir(root)
+        .ok()?
+        .filter_map(Result::ok)
+        .map(|entry| entry.path())
+        .filter(|path| {
+            path.file_name()
+                .and_then(std::ffi::OsStr::to_str)
+                .is_some_and(|name| name.starts_with("BAT"))
+        })
+        .collect::<Vec<_>>();
+    batteries.sort_unstable();
+
+    let (capacity, status) = batteries.iter().find_map(|battery| {
+        let capacity = std::fs::read_to_string(battery.join("capacity"))
+            .ok()?
+            .trim()
+            .parse::<u8>()
+            .ok()?;
+        let status = std::fs::read_to_string(battery.join("status")).unwrap_or_default();
+        Some((capacity.min(100), status))
+    })?;
+
+    let battery = Battery {
+        capacity,
+        discharging: status.trim() == "Discharging",
+        low: capacity <= threshold,
+    };
+
+    (battery.discharging || battery.low).then_some(battery)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Fixture;
+
+    #[test]
+    fn no_battery() {
+        let fixture = Fixture::new("battery-none", &[("AC/online", "1\n")]);
+        assert_eq!(status_in(&fixture.0, 20), None);
+        assert_eq!(status_in(&fixture.0.join("missing"), 20), None);
+    }
+
+    #[test]
+    fn charging() {
+        let fixture = Fixture::new(
+            "battery-charging",
+            &[("BAT0/capacity", "80\n"), ("BAT0/status", "Charging\n")],
+        );
+        assert_eq!(status_in(&fixture.0, 20), None);
+    }
+
+    #[test]
+    fn discharging() {
+        let fixture = Fixture::new(
+            "battery-discharging",
+            &[
+                ("AC/online", "0\n"),
+                ("BAT1/capacity", "60\n"),
+                ("BAT1/status", "Discharging\n"),
+            ],
+        );
+        assert_eq!(
+            status_in(&fixture.0, 20),
+            Some(Battery {
+                capacity: 60,
+                discharging: true,
+                low: false,
+            })
+        );
+    }
+
+    #[test]
+    fn threshold() {
+        let fixture = Fixture::new(
+            "battery-threshold",
+            &[
+                ("BAT0/capacity", "15\n"),
+                ("BAT0/status", "Not charging\n"),
+                ("BAT1/capacity", "90\n"),
+            ],
+        );
+        assert_eq!(
+            status_in(&fixture.0, 20),
+            Some(Battery {
+                capacity: 15,
+                discharging: false,
+                low: true,
+            })
+        );
+        assert_eq!(status_in(&fixture.0, 10), None);
+    }
+}
@@ -0,0 +1,105 @@
+use simd_json::prelude::ValueAsScalar;
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Aws {
+    pub profile: Option<String>,
+    pub region: Option<String>,
+}
+
+pub fn aws() -> Option<Aws> {
+    let var = |name| std::env::var(name).ok().filter(|value| !value.is_empty());
+
+    let profile = var("AWS_PROFILE").or_else(|| var("AWS_DEFAULT_PROFILE"));
+    let region = var("AWS_REGION").or_else(|| var("AWS_DEFAULT_REGION"));
+
+    (profile.is_some() || region.is_some()).then_some(Aws { profile, region })
+}
+
+pub fn gcp() -> Option<String> {
+    if let Some(config) = std::env::var("CLOUDSDK_ACTIVE_CONFIG_NAME")
+        .ok()
+        .filter(|config| !config.is_empty())
+    {
+        return Some(config);
+    }
+
+    let dir = std::env::var_os("CLOUDSDK_CONFIG")
+        .map(std::path::PathBuf::from)
+        .or_else(|| {
+            std::env::var_os("HOME").map(|home| {
+                std::path::PathBuf::from(home)
+                    .join(".config")
+                    .join("gcloud")
+            })
+        })?;
+
+    std::fs::read_to_string(dir.join("active_config"))
+        .ok()
+        .map(|config| String::from(config.trim()))
+        .filter(|config| !config.is_empty())
+}
+
+pub fn azure() -> Option<String> {
+    let dir = std::env::var_os("AZURE_CONFIG_DIR")
+        .map(std::path::PathBuf::from)
+        .or_else(|| {
+            std::env::var_os("HOME").map(|home| std::path::PathBuf::from(home).join(".azure"))
+        })?;
+
+    let mut profile = std::fs::read(dir.join("azureProfile.json")).ok()?;
+    default_subscription(&mut profile)
+}
+
+fn default_subscription(profile: &mut Vec<u8>) -> Option<String> {
+    // The Azure CLI writes the profile with a byte order mark
+    if profile.starts_with(b"\xef\xbb\xbf") {
+        profile.drain(..3);
+    }
+
+    let tape = simd_json::to_tape(profile).ok()?;
+    let subscriptions = tape.as_value().get("subscriptions")?.as_array()?;
+
+    subscriptions
+        .iter()
+        .find(|subscription| {
+            subscription
+                .get("isDefault")
+                .and_then(|default| default.as_bool())
+                .unwrap_or(false)
+        })
+        .and_then(|subscription| subscription.get("name"))
+        .and_then(|name| name.as_str().map(String::from))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn azure_default() {
+        let mut profile = Vec::from(
+            &b"\xef\xbb\xbf{\"installationId\": \"abc\", \"subscriptions\": [
+                {\"id\": \"1\", \"name\": \"Dev\", \"isDefault\": false},
+                {\"id\": \"2\", \"name\": \"Production\", \"isDefault\": true}
+            ]}"[..],
+        );
+        assert_eq!(
+            default_subscription(&mut profile),
+            Some(String::from("Production"))
+        );
+    }
+
+    #[test]
+    fn azure_no_default() {
+        let mut profile = Vec::from(
+            &b"{\"subscriptions\": [{\"id\": \"1\", \"name\": \"Dev\", \"isDefault\": false}]}"[..],
+        );
+        assert_eq!(default_subscription(&mut profile), None);
+
+        let mut profile = Vec::from(&b"{\"subscriptions\": []}"[..]);
+        assert_eq!(default_subscription(&mut profile), None);
+
+        let mut profile = Vec::from(&b"not json"[..]);
+        assert_eq!(default_subscription(&mut profile), None);
+    }
+}
@@ -0,0 +1,85 @@
+use super::left::direnv;
+use crate::{Result, compat};
+
+pub fn render<Out>(out: Out) -> Result
+where
+    Out: std::io::Write,
+{
+    render_inner(
+        compat::Plain::from_env(compat::Theme::from_env(compat::Downgrade::from_env(out))),
+        direnv::changes(),
+    )
+}
+
+fn render_inner<Out>(mut out: Out, changes: Option<direnv::Changes>) -> Result
+where
+    Out: std::io::Write,
+{
+    let Some(changes) = changes else {
+        writeln!(out, "No direnv environment loaded")?;
+        return out.flush();
+    };
+
+    for name in changes.added {
+        writeln!(
+            out,
+            style!(fg = color!(green), "+ {name}", style!(reset)),
+            name = name
+        )?;
+    }
+
+    for name in changes.changed {
+        writeln!(
+            out,
+            style!(fg = color!(yellow), "~ {name}", style!(reset)),
+            name = name
+        )?;
+    }
+
+    for name in changes.removed {
+        writeln!(
+            out,
+            style!(fg = color!(red), "- {name}", style!(reset)),
+            name = name
+        )?;
+    }
+
+    out.flush()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test;
+
+    #[test]
+    fn not_loaded() {
+        let result = test(|s| render_inner(s, None));
+        assert_eq!(result, "No direnv environment loaded\n");
+    }
+
+    #[test]
+    fn changes() {
+        let result = test(|s| {
+            render_inner(
+                s,
+                Some(direnv::Changes {
+                    added: vec![String::from("FOO")],
+                    changed: vec![String::from("PATH")],
+                    removed: vec![String::from("BAR"), String::from("BAZ")],
+                }),
+            )
+        });
+        let expected = concat!(
+            style!(fg = color!(green), "+ FOO", style!(reset)),
+            "\n",
+            style!(fg = color!(yellow), "~ PATH", style!(reset)),
+            "\n",
+            style!(fg = color!(red), "- BAR", style!(reset)),
+            "\n",
+            style!(fg = color!(red), "- BAZ", style!(reset)),
+            "\n",
+        );
+        assert_eq!(result, expected);
+    }
+}
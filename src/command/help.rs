@@ -26,21 +26,123 @@ where
     writeln!(out, "  r       Generate right side prompt")?;
     writeln!(out, "  l       Generate left side prompt")?;
     writeln!(out, "  t       Generate tmux right side status")?;
+    writeln!(out, "  d       List the variables changed by direnv")?;
+    writeln!(out, "  j       Print the prompt state as JSON")?;
     writeln!(out, "  v       Print the current version")?;
     writeln!(out, "  h       Show this help message")?;
     writeln!(out)?;
     writeln!(out, "Arguments for `r` command:")?;
+    writeln!(out, "  -k      Show the current kubernetes context")?;
+    writeln!(
+        out,
+        "  -d      Show the current docker context if not default"
+    )?;
+    writeln!(out, "  -a      Show the current AWS profile and region")?;
+    writeln!(out, "  -g      Show the active GCP configuration")?;
+    writeln!(out, "  -m      Show the default Azure subscription")?;
+    writeln!(
+        out,
+        "  -t      Show the toolchain version pinned by the project"
+    )?;
+    writeln!(
+        out,
+        "  -b      Show the battery level when discharging or low"
+    )?;
     writeln!(out, "  -z      Print escape codes compatible with zsh")?;
     writeln!(out, "  -w<SUB> Replace black background with SUB")?;
     writeln!(out)?;
+    writeln!(out, "Environment for `r` command:")?;
+    writeln!(
+        out,
+        "  SIMPALT_KUBE_PRODUCTION    Comma separated context patterns to highlight (e.g. prod-*)"
+    )?;
+    writeln!(
+        out,
+        "  SIMPALT_BATTERY_THRESHOLD  Battery percentage considered low [20]"
+    )?;
+    writeln!(out)?;
     writeln!(out, "Arguments for `l` command:")?;
-    writeln!(out, "  HOST    Symbol to be used as host (can be escaped)",)?;
+    writeln!(out, "  HOST    Symbol to be used as host (can be escaped)")?;
+    writeln!(out, "          Defaults to USER@HOSTNAME in SSH sessions")?;
+    writeln!(
+        out,
+        "          Printed as is with -z, so zsh prompt escapes such as %m are not expanded"
+    )?;
     writeln!(out, "  -e      Last command was an error")?;
     writeln!(out, "  -j      There are background processes running")?;
     writeln!(out, "  -l      Use the long format")?;
+    writeln!(
+        out,
+        "  -c<COL> Terminal width used to shorten the long format path"
+    )?;
+    writeln!(
+        out,
+        "  -p<PCT> Percentage of the width the long format may use [50]"
+    )?;
     writeln!(out, "  -z      Print escape codes compatible with zsh")?;
     writeln!(out, "  -w<SUB> Replace black background with SUB")?;
     writeln!(out)?;
+    writeln!(out, "Environment for `l` command:")?;
+    writeln!(
+        out,
+        "  SIMPALT_ALIASES      List of ALIAS=PATH to show PATH as ALIAS (separated like $PATH)"
+    )?;
+    writeln!(
+        out,
+        "  SIMPALT_REMOTE_HOST  Symbol to be used as host in SSH sessions"
+    )?;
+    writeln!(
+        out,
+        "  SIMPALT_CONDA_BASE   Show the conda `base` environment when set"
+    )?;
+    writeln!(
+        out,
+        "  SIMPALT_DIRENV_DIFF  Show the number of variables changed by direnv when set"
+    )?;
+    writeln!(
+        out,
+        "  SIMPALT_DIRENV_HOOK  Show a blocked `.envrc` when set (exported by the zsh loaders only)"
+    )?;
+    writeln!(out)?;
+    render_tmux(&mut out)?;
+    writeln!(out)?;
+    render_environment(&mut out)
+}
+
+fn render_tmux<Out>(out: &mut Out) -> Result
+where
+    Out: std::io::Write,
+{
+    writeln!(out, "Usage for `t` command:")?;
+    writeln!(out, "  t PWD [ARGS]                      Right side status")?;
+    writeln!(out, "  t left [ARGS]                     Left side status")?;
+    writeln!(out, "  t window INDEX NAME ACTIVE PATH   Window status")?;
+    writeln!(out)?;
     writeln!(out, "Arguments for `t` command:")?;
-    writeln!(out, "  PWD     Working directory for command",)
+    writeln!(out, "  PWD     Working directory for command")?;
+    writeln!(out, "  -l      Show the 1-minute load average")?;
+    writeln!(out, "  -m      Show the memory usage")?;
+    writeln!(out, "  -s<SES> Show SES as the session name")?;
+    writeln!(out, "  -h      Show the hostname")?;
+    writeln!(out, "  -c      Show the clock")
+}
+
+fn render_environment<Out>(out: &mut Out) -> Result
+where
+    Out: std::io::Write,
+{
+    writeln!(out, "Environment:")?;
+    writeln!(
+        out,
+        "  SIMPALT_SYMBOLS  Symbol set to use: nerd, unicode or ascii [nerd]"
+    )?;
+    writeln!(
+        out,
+        "  SIMPALT_THEME    Palette to use: dark, light, solarized or high-contrast [dark]"
+    )?;
+    writeln!(out, "  NO_COLOR         Strip all colors when set")?;
+    writeln!(
+        out,
+        "  COLORTERM        Keep 24-bit colors when truecolor, otherwise downgrade based on TERM"
+    )
 }
@@ -0,0 +1,303 @@
+use super::left::{direnv, python};
+use crate::Result;
+use crate::git::long as git;
+
+pub fn render<Out>(out: Out) -> Result
+where
+    Out: std::io::Write,
+{
+    render_inner(out, &SysEnv)
+}
+
+fn render_inner<Out, Env>(mut out: Out, enver: &Env) -> Result
+where
+    Out: std::io::Write,
+    Env: EnvFetcher,
+{
+    let pwd = enver.pwd();
+
+    write!(out, "{{\"pwd\":")?;
+    match pwd.as_ref().and_then(|pwd| pwd.to_str()) {
+        Some(pwd) => string(&mut out, pwd)?,
+        None => write!(out, "null")?,
+    }
+
+    write!(out, ",\"venv\":")?;
+    match enver.venv() {
+        Some(venv) => {
+            write!(out, "{{\"kind\":")?;
+            match venv.kind {
+                python::Kind::Virtual => string(&mut out, "virtual")?,
+                python::Kind::Conda => string(&mut out, "conda")?,
+            }
+            write!(out, ",\"name\":")?;
+            string(&mut out, venv.name())?;
+            write!(out, ",\"path\":")?;
+            string(&mut out, &venv.path)?;
+            write!(out, ",\"version\":")?;
+            match venv.version {
+                Some(ref version) => string(&mut out, version)?,
+                None => write!(out, "null")?,
+            }
+            write!(out, "}}")?;
+        }
+        None => write!(out, "null")?,
+    }
+
+    write!(out, ",\"direnv\":")?;
+    match enver.direnv(pwd.as_deref()) {
+        Some((dir, state)) => {
+            write!(out, "{{\"dir\":")?;
+            string(&mut out, &dir)?;
+            write!(out, ",\"state\":")?;
+            string(
+                &mut out,
+                match state {
+                    direnv::State::Loaded => "loaded",
+                    direnv::State::Inactive => "inactive",
+                    direnv::State::Stale => "stale",
+                    direnv::State::Blocked => "blocked",
+                },
+            )?;
+            write!(out, "}}")?;
+        }
+        None => write!(out, "null")?,
+    }
+
+    write!(out, ",\"git\":")?;
+    match pwd {
+        Some(ref pwd) => repo(&mut out, enver.repo(pwd))?,
+        None => write!(out, "null")?,
+    }
+
+    writeln!(out, "}}")?;
+    out.flush()
+}
+
+fn repo<Out>(out: &mut Out, repo: git::Repo) -> Result
+where
+    Out: std::io::Write,
+{
+    let changes = match repo {
+        git::Repo::None => return write!(out, "null"),
+        git::Repo::Error => return write!(out, "{{\"state\":\"error\"}}"),
+        git::Repo::Regular(head, sync, changes) => {
+            write!(out, "{{\"state\":\"regular\",\"head\":")?;
+            string(out, &head)?;
+            write!(out, ",\"sync\":")?;
+            match sync {
+                git::Sync::Local => write!(out, "{{\"state\":\"local\"}}")?,
+                git::Sync::Gone => write!(out, "{{\"state\":\"gone\"}}")?,
+                git::Sync::Tracked { ahead, behind } => write!(
+                    out,
+                    "{{\"state\":\"tracked\",\"ahead\":{ahead},\"behind\":{behind}}}"
+                )?,
+            }
+            changes
+        }
+        git::Repo::Detached(head, changes) => {
+            write!(out, "{{\"state\":\"detached\",\"head\":")?;
+            string(out, &head)?;
+            changes
+        }
+        git::Repo::Pending(head, pending, changes) => {
+            write!(out, "{{\"state\":\"pending\",\"head\":")?;
+            string(out, &head)?;
+            write!(out, ",\"pending\":")?;
+            string(
+                out,
+                match pending {
+                    git::Pending::Merge => "merge",
+                    git::Pending::Revert => "revert",
+                    git::Pending::Cherry => "cherry",
+                    git::Pending::Bisect => "bisect",
+                    git::Pending::Rebase => "rebase",
+                    git::Pending::Mailbox => "mailbox",
+                },
+            )?;
+            changes
+        }
+        git::Repo::New(changes) => {
+            write!(out, "{{\"state\":\"new\"")?;
+            changes
+        }
+    };
+
+    write!(
+        out,
+        ",\"changes\":{{\"added\":{added},\"removed\":{removed},\"modified\":{modified},\"conflicted\":{conflicted}}}}}",
+        added = changes.added,
+        removed = changes.removed,
+        modified = changes.modified,
+        conflicted = changes.conflicted,
+    )
+}
+
+fn string<Out>(out: &mut Out, value: &str) -> Result
+where
+    Out: std::io::Write,
+{
+    write!(out, "\"")?;
+    for c in value.chars() {
+        match c {
+            '"' => write!(out, "\\\"")?,
+            '\\' => write!(out, "\\\\")?,
+            '\n' => write!(out, "\\n")?,
+            '\r' => write!(out, "\\r")?,
+            '\t' => write!(out, "\\t")?,
+            c if c.is_control() => write!(out, "\\u{code:04x}", code = u32::from(c))?,
+            c => write!(out, "{c}")?,
+        }
+    }
+    write!(out, "\"")
+}
+
+trait EnvFetcher {
+    fn pwd(&self) -> Option<std::path::PathBuf>;
+    fn venv(&self) -> Option<python::Venv>;
+    fn direnv(&self, pwd: Option<&std::path::Path>) -> Option<(String, direnv::State)>;
+    fn repo(&self, pwd: &std::path::Path) -> git::Repo;
+}
+
+#[derive(Copy, Clone)]
+struct SysEnv;
+
+impl EnvFetcher for SysEnv {
+    fn pwd(&self) -> Option<std::path::PathBuf> {
+        std::env::current_dir()
+            .ok()
+            .or_else(|| std::env::var_os("PWD").map(std::path::PathBuf::from))
+    }
+
+    fn venv(&self) -> Option<python::Venv> {
+        python::venv().map(python::Venv::with_version)
+    }
+
+    fn direnv(&self, pwd: Option<&std::path::Path>) -> Option<(String, direnv::State)> {
+        direnv::detect(pwd)
+    }
+
+    fn repo(&self, pwd: &std::path::Path) -> git::Repo {
+        git::parse(&pwd.to_path_buf())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test;
+
+    struct MockEnv {
+        pwd: Option<std::path::PathBuf>,
+        venv: Option<python::Venv>,
+        direnv: Option<(String, direnv::State)>,
+        repo: git::Repo,
+    }
+
+    impl Default for MockEnv {
+        fn default() -> Self {
+            Self {
+                pwd: None,
+                venv: None,
+                direnv: None,
+                repo: git::Repo::None,
+            }
+        }
+    }
+
+    impl EnvFetcher for MockEnv {
+        fn pwd(&self) -> Option<std::path::PathBuf> {
+            self.pwd.clone()
+        }
+
+        fn venv(&self) -> Option<python::Venv> {
+            self.venv.clone()
+        }
+
+        fn direnv(&self, _: Option<&std::path::Path>) -> Option<(String, direnv::State)> {
+            self.direnv.clone()
+        }
+
+        fn repo(&self, _: &std::path::Path) -> git::Repo {
+            self.repo.clone()
+        }
+    }
+
+    #[test]
+    fn empty() {
+        let result = test(|s| render_inner(s, &MockEnv::default()));
+        assert_eq!(
+            result,
+            "{\"pwd\":null,\"venv\":null,\"direnv\":null,\"git\":null}\n"
+        );
+    }
+
+    #[test]
+    fn full() {
+        let result = test(|s| {
+            render_inner(
+                s,
+                &MockEnv {
+                    pwd: Some(std::path::PathBuf::from("/some/\"quoted\" path")),
+                    venv: Some(python::Venv {
+                        kind: python::Kind::Virtual,
+                        path: String::from("/some/venv"),
+                        version: Some(String::from("3.12")),
+                    }),
+                    direnv: Some((String::from("/some"), direnv::State::Stale)),
+                    repo: git::Repo::Regular(
+                        String::from("main"),
+                        git::Sync::Tracked {
+                            ahead: 2,
+                            behind: 0,
+                        },
+                        git::Changes {
+                            added: 1,
+                            modified: 3,
+                            removed: 0,
+                            conflicted: 0,
+                        },
+                    ),
+                },
+            )
+        });
+        assert_eq!(
+            result,
+            concat!(
+                "{\"pwd\":\"/some/\\\"quoted\\\" path\",",
+                "\"venv\":{\"kind\":\"virtual\",\"name\":\"venv\",\"path\":\"/some/venv\",\"version\":\"3.12\"},",
+                "\"direnv\":{\"dir\":\"/some\",\"state\":\"stale\"},",
+                "\"git\":{\"state\":\"regular\",\"head\":\"main\",",
+                "\"sync\":{\"state\":\"tracked\",\"ahead\":2,\"behind\":0},",
+                "\"changes\":{\"added\":1,\"removed\":0,\"modified\":3,\"conflicted\":0}}}\n"
+            )
+        );
+    }
+
+    #[test]
+    fn pending() {
+        let result = test(|s| {
+            repo(
+                s,
+                git::Repo::Pending(
+                    String::from("abc123"),
+                    git::Pending::Rebase,
+                    git::Changes::default(),
+                ),
+            )
+        });
+        assert_eq!(
+            result,
+            concat!(
+                "{\"state\":\"pending\",\"head\":\"abc123\",\"pending\":\"rebase\",",
+                "\"changes\":{\"added\":0,\"removed\":0,\"modified\":0,\"conflicted\":0}}"
+            )
+        );
+    }
+
+    #[test]
+    fn escapes() {
+        let result = test(|s| string(s, "tab\there\u{1b}"));
+        assert_eq!(result, "\"tab\\there\\u001b\"");
+    }
+}
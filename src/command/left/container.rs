@@ -0,0 +1,157 @@
+pub fn detect() -> Option<String> {
+    detect_in(std::path::Path::new("/"), |var| std::env::var(var).ok())
+}
+
+fn detect_in<F>(root: &std::path::Path, var: F) -> Option<String>
+where
+    F: Fn(&str) -> Option<String>,
+{
+    let var = |name| var(name).filter(|value| !value.is_empty());
+
+    if let Some(distro) = var("WSL_DISTRO_NAME") {
+        return Some(distro);
+    }
+
+    // Set by distrobox
+    if let Some(name) = var("CONTAINER_ID") {
+        return Some(name);
+    }
+
+    // Podman and toolbox
+    if let Ok(env) = std::fs::read_to_string(root.join("run/.containerenv")) {
+        return Some(containerenv_name(&env).map_or_else(
+            || {
+                if root.join("run/.toolboxenv").exists() {
+                    String::from("toolbox")
+                } else {
+                    String::from("podman")
+                }
+            },
+            String::from,
+        ));
+    }
+
+    if root.join(".dockerenv").exists() {
+        return Some(String::from("docker"));
+    }
+
+    // Set by systemd-nspawn and other systemd aware managers
+    if let Some(manager) = std::fs::read_to_string(root.join("run/systemd/container"))
+        .ok()
+        .map(|manager| String::from(manager.trim()))
+        .filter(|manager| !manager.is_empty())
+        .or_else(|| var("container"))
+    {
+        return Some(manager);
+    }
+
+    std::fs::read_to_string(root.join("proc/1/cgroup"))
+        .ok()
+        .and_then(|cgroup| cgroup_hint(&cgroup))
+        .map(String::from)
+}
+
+fn containerenv_name(env: &str) -> Option<&str> {
+    env.lines()
+        .find_map(|line| line.strip_prefix("name="))
+        .map(|name| name.trim().trim_matches('"'))
+        .filter(|name| !name.is_empty())
+}
+
+fn cgroup_hint(cgroup: &str) -> Option<&'static str> {
+    if cgroup.contains("kubepods") {
+        Some("kubernetes")
+    } else if cgroup.contains("docker") {
+        Some("docker")
+    } else if cgroup.contains("lxc") {
+        Some("lxc")
+    } else if cgroup.contains("containerd") {
+        Some("containerd")
+    } else {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Fixture;
+
+    fn no_var(_: &str) -> Option<String> {
+        None
+    }
+
+    #[test]
+    fn bare_metal() {
+        let fixture = Fixture::new("container-bare", &[("proc/1/cgroup", "0::/init.scope\n")]);
+        assert_eq!(detect_in(&fixture.0, no_var), None);
+    }
+
+    #[test]
+    fn env_vars() {
+        let fixture = Fixture::new("container-env", &[(".dockerenv", "")]);
+        assert_eq!(
+            detect_in(&fixture.0, |var| (var == "WSL_DISTRO_NAME")
+                .then(|| String::from("Ubuntu"))),
+            Some(String::from("Ubuntu"))
+        );
+        assert_eq!(
+            detect_in(&fixture.0, |var| (var == "CONTAINER_ID")
+                .then(|| String::from("arch"))),
+            Some(String::from("arch"))
+        );
+        assert_eq!(
+            detect_in(&fixture.0, |var| (var == "WSL_DISTRO_NAME")
+                .then(String::new)),
+            Some(String::from("docker"))
+        );
+    }
+
+    #[test]
+    fn podman() {
+        let fixture = Fixture::new(
+            "container-podman",
+            &[(
+                "run/.containerenv",
+                "engine=\"podman-4.9.3\"\nname=\"dev-box\"\nid=\"abc\"\n",
+            )],
+        );
+        assert_eq!(detect_in(&fixture.0, no_var), Some(String::from("dev-box")));
+
+        let fixture = Fixture::new("container-podman-anonymous", &[("run/.containerenv", "")]);
+        assert_eq!(detect_in(&fixture.0, no_var), Some(String::from("podman")));
+    }
+
+    #[test]
+    fn toolbox() {
+        let fixture = Fixture::new(
+            "container-toolbox",
+            &[("run/.containerenv", ""), ("run/.toolboxenv", "")],
+        );
+        assert_eq!(detect_in(&fixture.0, no_var), Some(String::from("toolbox")));
+    }
+
+    #[test]
+    fn nspawn() {
+        let fixture = Fixture::new(
+            "container-nspawn",
+            &[("run/systemd/container", "systemd-nspawn\n")],
+        );
+        assert_eq!(
+            detect_in(&fixture.0, no_var),
+            Some(String::from("systemd-nspawn"))
+        );
+    }
+
+    #[test]
+    fn cgroup() {
+        let fixture = Fixture::new(
+            "container-cgroup",
+            &[("proc/1/cgroup", "12:pids:/kubepods/besteffort/pod1\n")],
+        );
+        assert_eq!(
+            detect_in(&fixture.0, no_var),
+            Some(String::from("kubernetes"))
+        );
+    }
+}
@@ -1,4 +1,57 @@
-pub fn is_active() -> Option<bool> {
+use simd_json::prelude::ValueAsScalar;
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum State {
+    Loaded,
+    Inactive,
+    Stale,
+    Blocked,
+}
+
+#[derive(Debug, Default, Eq, PartialEq)]
+pub struct Changes {
+    pub added: Vec<String>,
+    pub changed: Vec<String>,
+    pub removed: Vec<String>,
+}
+
+impl Changes {
+    pub fn count(&self) -> usize {
+        self.added.len() + self.changed.len() + self.removed.len()
+    }
+}
+
+pub fn detect(pwd: Option<&std::path::Path>) -> Option<(String, State)> {
+    detect_in(pwd, |var| std::env::var(var).ok())
+}
+
+fn detect_in<F>(pwd: Option<&std::path::Path>, var: F) -> Option<(String, State)>
+where
+    F: Fn(&str) -> Option<String>,
+{
+    let var = |name| var(name).filter(|value| !value.is_empty());
+
+    if let Some(dir) = var("DIRENV_DIR") {
+        let state = if is_stale().unwrap_or(false) {
+            State::Stale
+        } else if is_active().unwrap_or(false) {
+            State::Loaded
+        } else {
+            State::Inactive
+        };
+
+        // direnv marks the directory with a leading `-`, which is not part of the path
+        let dir = String::from(dir.strip_prefix('-').unwrap_or(&dir));
+        return Some((dir, state));
+    }
+
+    // A blocked `.envrc` leaves nothing behind in the environment, so only the shell
+    // integration can tell that direnv would have loaded it
+    var("SIMPALT_DIRENV_HOOK")?;
+    find_envrc(pwd?).map(|dir| (dir.to_string_lossy().into_owned(), State::Blocked))
+}
+
+fn is_active() -> Option<bool> {
     let diff = std::env::var("DIRENV_DIFF").ok()?;
 
     let mut decoded = decode(&diff)?;
@@ -7,6 +60,24 @@ pub fn is_active() -> Option<bool> {
     detect_active(&parsed)
 }
 
+pub fn changes() -> Option<Changes> {
+    let diff = std::env::var("DIRENV_DIFF").ok()?;
+
+    let mut decoded = decode(&diff)?;
+    let parsed = simd_json::to_tape(&mut decoded).ok()?;
+
+    detect_changes(&parsed)
+}
+
+fn is_stale() -> Option<bool> {
+    let watches = std::env::var("DIRENV_WATCHES").ok()?;
+
+    let mut decoded = decode(&watches)?;
+    let parsed = simd_json::to_tape(&mut decoded).ok()?;
+
+    detect_stale(&parsed)
+}
+
 fn decode(diff: &str) -> Option<Vec<u8>> {
     let decoded =
         base64::Engine::decode(&base64::engine::general_purpose::URL_SAFE, diff.trim()).ok()?;
@@ -21,3 +92,211 @@ fn decode(diff: &str) -> Option<Vec<u8>> {
 fn detect_active(tape: &simd_json::Tape<'_>) -> Option<bool> {
     tape.as_value().get("p")?.as_object().map(|p| !p.is_empty())
 }
+
+// `p` holds the values from before the `.envrc` was loaded and `n` the ones after it,
+// so a variable only in `n` was added and one only in `p` was removed
+fn detect_changes(tape: &simd_json::Tape<'_>) -> Option<Changes> {
+    let diff = tape.as_value();
+    let names = |key| {
+        diff.get(key).and_then(|map| {
+            map.as_object().map(|map| {
+                let mut names = map
+                    .iter()
+                    .map(|(name, _)| name)
+                    .filter(|name| !name.starts_with("DIRENV_"))
+                    .map(String::from)
+                    .collect::<Vec<_>>();
+                names.sort_unstable();
+                names
+            })
+        })
+    };
+
+    let previous = names("p");
+    let next = names("n");
+    if previous.is_none() && next.is_none() {
+        return None;
+    }
+    let previous = previous.unwrap_or_default();
+    let next = next.unwrap_or_default();
+
+    let (changed, added) = next
+        .into_iter()
+        .partition::<Vec<_>, _>(|name| previous.contains(name));
+    let removed = previous
+        .into_iter()
+        .filter(|name| !changed.contains(name))
+        .collect();
+
+    Some(Changes {
+        added,
+        changed,
+        removed,
+    })
+}
+
+// Mirrors direnv's own check: any watched file whose existence or mtime no longer
+// matches what was recorded when the environment was loaded
+fn detect_stale(tape: &simd_json::Tape<'_>) -> Option<bool> {
+    let watches = tape.as_value().as_array()?;
+
+    Some(watches.iter().any(|watch| {
+        let Some(path) = watch.get("path") else {
+            return false;
+        };
+        let Some(path) = path.as_str() else {
+            return false;
+        };
+        let exists = watch
+            .get("exists")
+            .and_then(|exists| exists.as_bool())
+            .unwrap_or(false);
+        let modtime = watch.get("modtime").and_then(|modtime| modtime.as_i64());
+
+        match std::fs::metadata(path).and_then(|metadata| metadata.modified()) {
+            Ok(modified) => !exists || modtime != unix_seconds(modified),
+            Err(_) => exists,
+        }
+    }))
+}
+
+fn unix_seconds(time: std::time::SystemTime) -> Option<i64> {
+    time.duration_since(std::time::UNIX_EPOCH)
+        .ok()
+        .and_then(|duration| i64::try_from(duration.as_secs()).ok())
+}
+
+fn find_envrc(pwd: &std::path::Path) -> Option<&std::path::Path> {
+    pwd.ancestors().find(|dir| dir.join(".envrc").is_file())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Fixture;
+
+    fn fixture(name: &str) -> Fixture {
+        Fixture::new(
+            &format!("direnv-{name}"),
+            &[("project/.envrc", "use flake\n"), ("project/src/", "")],
+        )
+    }
+
+    fn envrc(fixture: &Fixture) -> std::path::PathBuf {
+        fixture.0.join("project").join(".envrc")
+    }
+
+    fn watch_list(fixture: &Fixture, modtime: Option<i64>, exists: bool) -> Vec<u8> {
+        format!(
+            "[{{\"path\": \"{path}\", \"modtime\": {modtime}, \"exists\": {exists}}}]",
+            path = envrc(fixture).display(),
+            modtime = modtime.unwrap_or_default(),
+        )
+        .into_bytes()
+    }
+
+    #[test]
+    fn changes() {
+        let mut diff = Vec::from(
+            &br#"{
+                "p": {"PATH": "/usr/bin", "OLD": "1", "DIRENV_WATCHES": "x"},
+                "n": {"PATH": "/nix/bin:/usr/bin", "NEW": "2", "ANOTHER": "3"}
+            }"#[..],
+        );
+        let tape = simd_json::to_tape(&mut diff).unwrap();
+        let changes = detect_changes(&tape).unwrap();
+        assert_eq!(
+            changes,
+            Changes {
+                added: vec![String::from("ANOTHER"), String::from("NEW")],
+                changed: vec![String::from("PATH")],
+                removed: vec![String::from("OLD")],
+            }
+        );
+        assert_eq!(changes.count(), 4);
+    }
+
+    #[test]
+    fn no_changes() {
+        let mut diff = Vec::from(&b"{\"p\": {}, \"n\": {}}"[..]);
+        let tape = simd_json::to_tape(&mut diff).unwrap();
+        assert_eq!(detect_changes(&tape), Some(Changes::default()));
+
+        let mut diff = Vec::from(&b"{}"[..]);
+        let tape = simd_json::to_tape(&mut diff).unwrap();
+        assert_eq!(detect_changes(&tape), None);
+    }
+
+    #[test]
+    fn envrc_up_the_tree() {
+        let fixture = fixture("find");
+        assert_eq!(
+            find_envrc(&fixture.0.join("project").join("src")),
+            Some(fixture.0.join("project").as_path())
+        );
+        assert_eq!(find_envrc(&fixture.0), None);
+    }
+
+    #[test]
+    fn dir_prefix() {
+        let loaded = |var: &str| (var == "DIRENV_DIR").then(|| String::from("-/some/project"));
+        assert_eq!(
+            detect_in(None, loaded).map(|(dir, _)| dir),
+            Some(String::from("/some/project"))
+        );
+    }
+
+    #[test]
+    fn blocked() {
+        let fixture = fixture("blocked");
+        let pwd = fixture.0.join("project").join("src");
+        let hooked = |var: &str| (var == "SIMPALT_DIRENV_HOOK").then(|| String::from("1"));
+
+        assert_eq!(
+            detect_in(Some(&pwd), hooked),
+            Some((
+                fixture.0.join("project").to_string_lossy().into_owned(),
+                State::Blocked
+            ))
+        );
+        assert_eq!(detect_in(Some(&fixture.0), hooked), None);
+        assert_eq!(detect_in(None, hooked), None);
+        assert_eq!(detect_in(Some(&pwd), |_| None), None);
+        assert_eq!(detect_in(Some(&pwd), |_| Some(String::new())), None);
+    }
+
+    #[test]
+    fn fresh() {
+        let fixture = fixture("fresh");
+        let modified = std::fs::metadata(envrc(&fixture))
+            .and_then(|metadata| metadata.modified())
+            .unwrap();
+
+        let mut watches = watch_list(&fixture, unix_seconds(modified), true);
+        let tape = simd_json::to_tape(&mut watches).unwrap();
+        assert_eq!(detect_stale(&tape), Some(false));
+    }
+
+    #[test]
+    fn stale() {
+        let fixture = fixture("stale");
+
+        let mut watches = watch_list(&fixture, Some(1), true);
+        let tape = simd_json::to_tape(&mut watches).unwrap();
+        assert_eq!(detect_stale(&tape), Some(true));
+
+        let mut watches = watch_list(&fixture, None, false);
+        let tape = simd_json::to_tape(&mut watches).unwrap();
+        assert_eq!(detect_stale(&tape), Some(true));
+    }
+
+    #[test]
+    fn removed_watch() {
+        let fixture = fixture("removed");
+        let mut watches = watch_list(&fixture, Some(1), true);
+        std::fs::remove_file(envrc(&fixture)).unwrap();
+
+        let tape = simd_json::to_tape(&mut watches).unwrap();
+        assert_eq!(detect_stale(&tape), Some(true));
+    }
+}
@@ -1,11 +1,19 @@
-use crate::Result;
+use super::{direnv, nix, python, user};
 use crate::git::long as git;
+use crate::{Result, symbols};
+use std::io::Write;
 
-pub fn render<Out>(out: Out, host: Option<String>, error: bool, jobs: bool) -> Result
+pub fn render<Out>(
+    out: Out,
+    host: Option<String>,
+    error: bool,
+    jobs: bool,
+    max_width: Option<usize>,
+) -> Result
 where
     Out: std::io::Write,
 {
-    render_inner(out, host, error, jobs, &SysEnv)
+    render_inner(out, host, error, jobs, max_width, &SysEnv)
 }
 
 fn render_inner<Out, Env>(
@@ -13,138 +21,235 @@ fn render_inner<Out, Env>(
     host: Option<String>,
     error: bool,
     jobs: bool,
+    max_width: Option<usize>,
     enver: &Env,
 ) -> Result
 where
     Out: std::io::Write,
     Env: EnvFetcher,
 {
+    let mut buffer = Vec::new();
+    let path = render_prompt(&mut buffer, host, error, jobs, enver)?;
+
+    if let Some(max_width) = max_width
+        && let Some((start, end)) = path
+    {
+        let excess = visible_width(&buffer).saturating_sub(max_width);
+        if excess > 0
+            && let Ok(pwd) = std::str::from_utf8(&buffer[start..end])
+        {
+            let max = pwd.chars().count().saturating_sub(excess);
+            out.write_all(&buffer[..start])?;
+            write!(out, "{pwd}", pwd = super::path::shorten(pwd, max))?;
+            out.write_all(&buffer[end..])?;
+            return out.flush();
+        }
+    }
+
+    out.write_all(&buffer)?;
+    out.flush()
+}
+
+fn render_prompt<Env>(
+    out: &mut Vec<u8>,
+    host: Option<String>,
+    error: bool,
+    jobs: bool,
+    enver: &Env,
+) -> Result<Option<(usize, usize)>>
+where
+    Env: EnvFetcher,
+{
+    let symbols = enver.symbols();
     let mut last = None;
 
+    if let Some(root) = enver.root() {
+        out.div(&mut last, symbols, color!(red), color!(black))?;
+        write!(out, "{} root", symbols.get(symbol!(root)))?;
+        if let user::Root::Sudo(user) = root {
+            write!(out, " ({user})")?;
+        }
+    }
+
     if error {
-        out.div(&mut last, color!(black), color!(red))?;
-        write!(out, symbol!(error))?;
+        out.div(&mut last, symbols, color!(black), color!(red))?;
+        write!(out, "{}", symbols.get(symbol!(error)))?;
     }
 
     if jobs {
-        out.div(&mut last, color!(black), color!(cyan))?;
-        write!(out, symbol!(jobs))?;
+        out.div(&mut last, symbols, color!(black), color!(cyan))?;
+        write!(out, "{}", symbols.get(symbol!(jobs)))?;
     }
 
-    if let Some(host) = host {
-        out.div(&mut last, color!(black), color!(reset))?;
+    if let Some(host) = host.or_else(|| enver.remote()) {
+        out.div(&mut last, symbols, color!(black), color!(reset))?;
         write!(out, "{host}")?;
         write!(out, style!(reset to bg = color!(black)))?;
     }
 
-    if let Some((direnv, active)) = enver.direnv() {
-        if active {
-            write!(out, style!(fg = color!(green)))?;
-        } else {
-            write!(out, style!(fg = color!(blue)))?;
+    if let Some(container) = enver.container() {
+        out.div(&mut last, symbols, color!(black), color!(magenta))?;
+        write!(out, "{} {container}", symbols.get(symbol!(container)))?;
+    }
+
+    let pwd = enver.pwd();
+
+    if let Some((direnv, state)) = enver.direnv(pwd.as_deref()) {
+        match state {
+            direnv::State::Loaded => write!(out, style!(fg = color!(green)))?,
+            direnv::State::Inactive => write!(out, style!(fg = color!(blue)))?,
+            direnv::State::Stale => write!(out, style!(fg = color!(yellow)))?,
+            direnv::State::Blocked => write!(out, style!(fg = color!(red)))?,
         }
-        write!(out, concat!(" ", symbol!(div thin), " "))?;
+        write!(out, " {} ", symbols.get(symbol!(div thin)))?;
         if let Some(direnv) = direnv.rsplit(std::path::MAIN_SEPARATOR).next() {
             write!(out, "{direnv}")?;
         } else {
             write!(out, "{direnv}")?;
         }
+        if let Some(count) = enver.direnv_count() {
+            write!(out, " ({count})")?;
+        }
     }
 
-    if let Some(venv) = enver.venv() {
-        out.div(&mut last, color!(cyan), color!(black))?;
-        if let Some(venv) = venv.rsplit(std::path::MAIN_SEPARATOR).next() {
-            write!(out, "{venv}")?;
+    if let Some(shell) = enver.nix_shell() {
+        if shell.pure {
+            write!(out, style!(fg = color!(cyan)))?;
         } else {
-            write!(out, "{venv}")?;
+            write!(out, style!(fg = color!(yellow)))?;
         }
+        write!(
+            out,
+            " {div} {nix} {name}",
+            div = symbols.get(symbol!(div thin)),
+            nix = symbols.get(symbol!(nix)),
+            name = shell.name.as_deref().unwrap_or("nix-shell"),
+        )?;
     }
 
-    let pwd = enver.pwd();
+    if let Some(venv) = enver.venv() {
+        out.div(&mut last, symbols, color!(cyan), color!(black))?;
+        if venv.kind == python::Kind::Conda {
+            write!(out, "{} ", symbols.get(symbol!(conda)))?;
+        }
+        write!(out, "{venv}", venv = venv.name())?;
+        if let Some(ref version) = venv.version {
+            write!(out, " py {version}")?;
+        }
+    }
 
-    out.div(&mut last, color!(blue), color!(black))?;
     if let Some(ref pwd) = pwd
-        && let Some(pwd) = pwd.to_str()
+        && let Some(workspace) = enver.terraform(pwd)
     {
-        if let Some(pwd) = enver.home().and_then(|home| pwd.strip_prefix(&home)) {
-            write!(out, "~{pwd}")?;
+        if workspace == "default" {
+            out.div(&mut last, symbols, color!(black), color!(magenta))?;
         } else {
-            write!(out, "{pwd}")?;
+            out.div(&mut last, symbols, color!(yellow), color!(black))?;
         }
+        write!(out, "{} {workspace}", symbols.get(symbol!(terraform)))?;
     }
 
+    out.div(&mut last, symbols, color!(blue), color!(black))?;
+    let path = match pwd {
+        Some(ref pwd) => render_path(out, pwd, enver)?,
+        None => None,
+    };
+
     if let Some(ref pwd) = pwd {
-        match git::parse(pwd) {
-            git::Repo::None => {}
-            git::Repo::Error => {
-                out.div(&mut last, color!(red), color!(black))?;
-                write!(out, "!")?;
-            }
-            git::Repo::Regular(head, sync, changes) => {
-                if changes.clean() {
-                    out.render_sync(&mut last, sync)?;
-                    out.div(&mut last, color!(green), color!(black))?;
-                    write!(out, concat!(symbol!(branch), "{head}"), head = head)?;
-                } else {
-                    out.render_changes(&mut last, changes)?;
-                    if !matches!(
-                        sync,
-                        git::Sync::Tracked {
-                            ahead: 0,
-                            behind: 0
-                        }
-                    ) {
-                        out.div(&mut last, color!(black), color!(reset))?;
-                        write!(out, symbol!(div thin))?;
-                        out.render_sync(&mut last, sync)?;
-                    }
-                    out.div(&mut last, color!(yellow), color!(black))?;
-                    write!(out, concat!(symbol!(branch), "{head}"), head = head)?;
-                }
-            }
-            git::Repo::Detached(head, changes) => {
-                out.render_changes(&mut last, changes)?;
-                out.div(&mut last, color!(magenta), color!(black))?;
-                write!(out, concat!(symbol!(ref), "{head}"), head = head)?;
-            }
-            git::Repo::Pending(head, pending, changes) => {
-                out.render_changes(&mut last, changes)?;
-                out.div(&mut last, color!(cyan), color!(black))?;
-                write!(
-                    out,
-                    concat!(symbol!(branch), "{head} {pending}"),
-                    head = head,
-                    pending = pending_symbol(pending),
-                )?;
-            }
-            git::Repo::New(changes) => {
-                out.render_changes(&mut last, changes)?;
-                out.div(&mut last, color!(cyan), color!(black))?;
-                write!(out, symbol!(new))?;
-            }
+        out.render_repo(&mut last, symbols, git::parse(pwd))?;
+    }
+    out.div(&mut last, symbols, color!(reset), color!(reset))?;
+    Ok(path)
+}
+
+fn render_path<Env>(
+    out: &mut Vec<u8>,
+    pwd: &std::path::Path,
+    enver: &Env,
+) -> Result<Option<(usize, usize)>>
+where
+    Env: EnvFetcher,
+{
+    if pwd.to_str().is_none() {
+        return Ok(None);
+    }
+
+    let mut aliases = enver.aliases();
+    if let Some(home) = enver.home() {
+        aliases.insert(0, (String::from("~"), std::path::PathBuf::from(home)));
+    }
+
+    if !enver.writable(pwd) {
+        write!(out, "{} ", enver.symbols().get(symbol!(lock)))?;
+    }
+
+    let start = out.len();
+    if let Some((alias, rest)) = super::path::substitute(pwd, &aliases) {
+        write!(out, "{alias}")?;
+        if let Some(rest) = rest.to_str().filter(|rest| !rest.is_empty()) {
+            write!(out, "{sep}{rest}", sep = std::path::MAIN_SEPARATOR)?;
         }
+    } else {
+        write!(out, "{pwd}", pwd = pwd.display())?;
     }
-    out.div(&mut last, color!(reset), color!(reset))?;
-    out.flush()
+    Ok(Some((start, out.len())))
+}
+
+// Every `char` is counted as a single cell, so wide characters are under-measured
+fn visible_width(buffer: &[u8]) -> usize {
+    let mut escaped = false;
+    String::from_utf8_lossy(buffer)
+        .chars()
+        .filter(|c| {
+            if escaped {
+                escaped = *c != 'm';
+                false
+            } else if *c == '' {
+                escaped = true;
+                false
+            } else {
+                true
+            }
+        })
+        .count()
 }
 
 trait Writer {
     fn div(
         &mut self,
         last: &mut Option<&'static str>,
+        symbols: symbols::Set,
         to: &'static str,
         fg: &'static str,
     ) -> Result;
 
-    fn render_changes(&mut self, last: &mut Option<&'static str>, changes: git::Changes) -> Result;
+    fn render_repo(
+        &mut self,
+        last: &mut Option<&'static str>,
+        symbols: symbols::Set,
+        repo: git::Repo,
+    ) -> Result;
+
+    fn render_changes(
+        &mut self,
+        last: &mut Option<&'static str>,
+        symbols: symbols::Set,
+        changes: git::Changes,
+    ) -> Result;
 
-    fn render_sync(&mut self, last: &mut Option<&'static str>, sync: git::Sync) -> Result;
+    fn render_sync(
+        &mut self,
+        last: &mut Option<&'static str>,
+        symbols: symbols::Set,
+        sync: git::Sync,
+    ) -> Result;
 }
 
 impl<W: std::io::Write> Writer for W {
     fn div(
         &mut self,
         last: &mut Option<&'static str>,
+        symbols: symbols::Set,
         to: &'static str,
         fg: &'static str,
     ) -> Result {
@@ -154,9 +259,10 @@ impl<W: std::io::Write> Writer for W {
             } else {
                 write!(
                     self,
-                    concat!(" [3{last};4{to}m", symbol!(div), "[3{fg}m "),
+                    " [3{last};4{to}m{div}[3{fg}m ",
                     last = last,
                     to = to,
+                    div = symbols.get(symbol!(div)),
                     fg = fg,
                 )?;
             }
@@ -167,47 +273,128 @@ impl<W: std::io::Write> Writer for W {
         Ok(())
     }
 
-    fn render_changes(&mut self, last: &mut Option<&'static str>, changes: git::Changes) -> Result {
+    fn render_repo(
+        &mut self,
+        last: &mut Option<&'static str>,
+        symbols: symbols::Set,
+        repo: git::Repo,
+    ) -> Result {
+        let branch = symbols.get(symbol!(branch));
+        match repo {
+            git::Repo::None => Ok(()),
+            git::Repo::Error => {
+                self.div(last, symbols, color!(red), color!(black))?;
+                write!(self, "!")
+            }
+            git::Repo::Regular(head, sync, changes) => {
+                if changes.clean() {
+                    self.render_sync(last, symbols, sync)?;
+                    self.div(last, symbols, color!(green), color!(black))?;
+                    write!(self, "{branch}{head}")
+                } else {
+                    self.render_changes(last, symbols, changes)?;
+                    if !matches!(
+                        sync,
+                        git::Sync::Tracked {
+                            ahead: 0,
+                            behind: 0
+                        }
+                    ) {
+                        self.div(last, symbols, color!(black), color!(reset))?;
+                        write!(self, "{}", symbols.get(symbol!(div thin)))?;
+                        self.render_sync(last, symbols, sync)?;
+                    }
+                    self.div(last, symbols, color!(yellow), color!(black))?;
+                    write!(self, "{branch}{head}")
+                }
+            }
+            git::Repo::Detached(head, changes) => {
+                self.render_changes(last, symbols, changes)?;
+                self.div(last, symbols, color!(magenta), color!(black))?;
+                write!(
+                    self,
+                    "{reference}{head}",
+                    reference = symbols.get(symbol!(ref))
+                )
+            }
+            git::Repo::Pending(head, pending, changes) => {
+                self.render_changes(last, symbols, changes)?;
+                self.div(last, symbols, color!(cyan), color!(black))?;
+                write!(
+                    self,
+                    "{branch}{head} {pending}",
+                    pending = symbols.get(pending_symbol(pending)),
+                )
+            }
+            git::Repo::New(changes) => {
+                self.render_changes(last, symbols, changes)?;
+                self.div(last, symbols, color!(cyan), color!(black))?;
+                write!(self, "{}", symbols.get(symbol!(new)))
+            }
+        }
+    }
+
+    fn render_changes(
+        &mut self,
+        last: &mut Option<&'static str>,
+        symbols: symbols::Set,
+        changes: git::Changes,
+    ) -> Result {
         if changes.added > 0 {
-            self.div(last, color!(black), color!(green))?;
+            self.div(last, symbols, color!(black), color!(green))?;
             write!(self, "+{added}", added = changes.added)?;
         }
 
         if changes.removed > 0 {
-            self.div(last, color!(black), color!(red))?;
+            self.div(last, symbols, color!(black), color!(red))?;
             write!(self, "-{removed}", removed = changes.removed)?;
         }
 
         if changes.modified > 0 {
-            self.div(last, color!(black), color!(blue))?;
+            self.div(last, symbols, color!(black), color!(blue))?;
             write!(self, "~{modified}", modified = changes.modified)?;
         }
 
         if changes.conflicted > 0 {
-            self.div(last, color!(black), color!(magenta))?;
+            self.div(last, symbols, color!(black), color!(magenta))?;
             write!(self, "!{conflicted}", conflicted = changes.conflicted)?;
         }
         Ok(())
     }
 
-    fn render_sync(&mut self, last: &mut Option<&'static str>, sync: git::Sync) -> Result {
+    fn render_sync(
+        &mut self,
+        last: &mut Option<&'static str>,
+        symbols: symbols::Set,
+        sync: git::Sync,
+    ) -> Result {
         match sync {
             git::Sync::Local => {
-                self.div(last, color!(black), color!(cyan))?;
-                write!(self, concat!(symbol!(local), " local"))
+                self.div(last, symbols, color!(black), color!(cyan))?;
+                write!(self, "{} local", symbols.get(symbol!(local)))
             }
             git::Sync::Gone => {
-                self.div(last, color!(black), color!(magenta))?;
-                write!(self, concat!(symbol!(gone), " gone"))
+                self.div(last, symbols, color!(black), color!(magenta))?;
+                write!(self, "{} gone", symbols.get(symbol!(gone)))
             }
             git::Sync::Tracked { ahead, behind } => {
                 if ahead > 0 {
-                    self.div(last, color!(black), color!(yellow))?;
-                    write!(self, concat!(symbol!(ahead), "{ahead}"), ahead = ahead)?;
+                    self.div(last, symbols, color!(black), color!(yellow))?;
+                    write!(
+                        self,
+                        "{symbol}{ahead}",
+                        symbol = symbols.get(symbol!(ahead)),
+                        ahead = ahead
+                    )?;
                 }
                 if behind > 0 {
-                    self.div(last, color!(black), color!(red))?;
-                    write!(self, concat!(symbol!(behind), "{behind}"), behind = behind)?;
+                    self.div(last, symbols, color!(black), color!(red))?;
+                    write!(
+                        self,
+                        "{symbol}{behind}",
+                        symbol = symbols.get(symbol!(behind)),
+                        behind = behind
+                    )?;
                 }
                 Ok(())
             }
@@ -229,8 +416,17 @@ const fn pending_symbol(pending: git::Pending) -> &'static str {
 trait EnvFetcher {
     fn pwd(&self) -> Option<std::path::PathBuf>;
     fn home(&self) -> Option<String>;
-    fn venv(&self) -> Option<String>;
-    fn direnv(&self) -> Option<(String, bool)>;
+    fn remote(&self) -> Option<String>;
+    fn root(&self) -> Option<user::Root>;
+    fn aliases(&self) -> Vec<(String, std::path::PathBuf)>;
+    fn writable(&self, path: &std::path::Path) -> bool;
+    fn venv(&self) -> Option<python::Venv>;
+    fn container(&self) -> Option<String>;
+    fn direnv(&self, pwd: Option<&std::path::Path>) -> Option<(String, direnv::State)>;
+    fn direnv_count(&self) -> Option<usize>;
+    fn nix_shell(&self) -> Option<nix::Shell>;
+    fn terraform(&self, pwd: &std::path::Path) -> Option<String>;
+    fn symbols(&self) -> symbols::Set;
 }
 
 #[derive(Copy, Clone)]
@@ -247,14 +443,51 @@ impl EnvFetcher for SysEnv {
         std::env::var("HOME").ok()
     }
 
-    fn venv(&self) -> Option<String> {
-        std::env::var("VIRTUAL_ENV").ok()
+    fn remote(&self) -> Option<String> {
+        super::user::remote()
     }
 
-    fn direnv(&self) -> Option<(String, bool)> {
-        std::env::var("DIRENV_DIR")
-            .ok()
-            .map(|d| (d, super::direnv::is_active().unwrap_or(false)))
+    fn root(&self) -> Option<user::Root> {
+        user::root()
+    }
+
+    fn aliases(&self) -> Vec<(String, std::path::PathBuf)> {
+        super::path::aliases()
+    }
+
+    fn writable(&self, path: &std::path::Path) -> bool {
+        super::path::writable(path)
+    }
+
+    fn venv(&self) -> Option<python::Venv> {
+        python::venv().map(python::Venv::with_version)
+    }
+
+    fn container(&self) -> Option<String> {
+        super::container::detect()
+    }
+
+    fn direnv(&self, pwd: Option<&std::path::Path>) -> Option<(String, direnv::State)> {
+        direnv::detect(pwd)
+    }
+
+    fn direnv_count(&self) -> Option<usize> {
+        std::env::var_os("SIMPALT_DIRENV_DIFF")
+            .filter(|enabled| !enabled.is_empty())
+            .and_then(|_| direnv::changes())
+            .map(|changes| changes.count())
+    }
+
+    fn nix_shell(&self) -> Option<nix::Shell> {
+        nix::shell()
+    }
+
+    fn terraform(&self, pwd: &std::path::Path) -> Option<String> {
+        super::terraform::workspace(pwd)
+    }
+
+    fn symbols(&self) -> symbols::Set {
+        symbols::Set::from_env()
     }
 }
 
@@ -267,8 +500,17 @@ mod tests {
     struct MockEnv {
         pwd: Option<std::path::PathBuf>,
         home: Option<String>,
-        venv: Option<String>,
-        direnv: Option<(String, bool)>,
+        remote: Option<String>,
+        root: Option<user::Root>,
+        aliases: Vec<(String, std::path::PathBuf)>,
+        readonly: bool,
+        venv: Option<python::Venv>,
+        container: Option<String>,
+        direnv: Option<(String, direnv::State)>,
+        direnv_count: Option<usize>,
+        nix_shell: Option<nix::Shell>,
+        terraform: Option<String>,
+        symbols: symbols::Set,
     }
 
     impl EnvFetcher for MockEnv {
@@ -280,18 +522,54 @@ mod tests {
             self.home.clone()
         }
 
-        fn venv(&self) -> Option<String> {
+        fn remote(&self) -> Option<String> {
+            self.remote.clone()
+        }
+
+        fn root(&self) -> Option<user::Root> {
+            self.root.clone()
+        }
+
+        fn aliases(&self) -> Vec<(String, std::path::PathBuf)> {
+            self.aliases.clone()
+        }
+
+        fn writable(&self, _: &std::path::Path) -> bool {
+            !self.readonly
+        }
+
+        fn venv(&self) -> Option<python::Venv> {
             self.venv.clone()
         }
 
-        fn direnv(&self) -> Option<(String, bool)> {
+        fn container(&self) -> Option<String> {
+            self.container.clone()
+        }
+
+        fn direnv(&self, _: Option<&std::path::Path>) -> Option<(String, direnv::State)> {
             self.direnv.clone()
         }
+
+        fn direnv_count(&self) -> Option<usize> {
+            self.direnv_count
+        }
+
+        fn nix_shell(&self) -> Option<nix::Shell> {
+            self.nix_shell.clone()
+        }
+
+        fn terraform(&self, _: &std::path::Path) -> Option<String> {
+            self.terraform.clone()
+        }
+
+        fn symbols(&self) -> symbols::Set {
+            self.symbols
+        }
     }
 
     #[test]
     fn all_empty() {
-        let result = test(|s| render_inner(s, None, false, false, &MockEnv::default()));
+        let result = test(|s| render_inner(s, None, false, false, None, &MockEnv::default()));
         let expected = concat!(
             // Missing error
             // Missing jobs
@@ -318,6 +596,7 @@ mod tests {
                 None,
                 false,
                 false,
+                None,
                 &MockEnv {
                     pwd: Some(std::path::PathBuf::from("/")),
                     ..MockEnv::default()
@@ -348,6 +627,7 @@ mod tests {
                 None,
                 false,
                 false,
+                None,
                 &MockEnv {
                     pwd: Some(std::path::PathBuf::from("/some/home/path/further/on")),
                     home: Some(String::from("/some/home/path")),
@@ -372,40 +652,39 @@ mod tests {
     }
 
     #[test]
-    fn all_tags() {
+    fn root() {
         let result = test(|s| {
             render_inner(
                 s,
-                Some(String::from("[31mH")),
-                true,
+                Some(String::from("H")),
                 true,
+                false,
+                None,
                 &MockEnv {
-                    pwd: Some(std::path::PathBuf::from("/some/home/path/further/on")),
-                    home: Some(String::from("/some/home/path")),
-                    venv: Some(String::from("py")),
-                    direnv: Some((String::from("/some/direnv"), false)),
+                    root: Some(user::Root::Login),
+                    ..MockEnv::default()
                 },
             )
         });
         let expected = concat!(
-            style!(fg = color!(red), bg = color!(black)),
+            style!(fg = color!(black), bg = color!(red)),
             " ",
-            symbol!(error),
+            symbol!(root),
+            " root ",
+            style!(fg = color!(red), bg = color!(black), symbol!(div)),
+            style!(fg = color!(red)),
             " ",
-            style!(fg = color!(cyan), symbol!(jobs)),
+            symbol!(error),
             " ",
-            style!(fg = color!(reset), style!(fg = color!(red), "H")),
+            style!(fg = color!(reset)),
+            "H",
             style!(reset to bg = color!(black)),
-            style!(fg = color!(blue)),
             " ",
-            symbol!(div thin),
-            " direnv ",
-            style!(fg = color!(black), bg = color!(cyan), symbol!(div)),
-            style!(fg = color!(black)),
-            " py ",
-            style!(fg = color!(cyan), bg = color!(blue), symbol!(div)),
+            style!(fg = color!(black), bg = color!(blue), symbol!(div)),
             style!(fg = color!(black)),
-            " ~/further/on ",
+            " ",
+            // Missing PWD
+            " ",
             style!(fg = color!(blue), bg = color!(reset), symbol!(div)),
             style!(fg = color!(reset)),
             " "
@@ -416,37 +695,30 @@ mod tests {
     }
 
     #[test]
-    fn direnv() {
+    fn root_sudo() {
         let result = test(|s| {
             render_inner(
                 s,
-                Some(String::from("[31mH")),
-                true,
-                true,
+                None,
+                false,
+                false,
+                None,
                 &MockEnv {
-                    pwd: Some(std::path::PathBuf::from("/some/home/path/further/on")),
-                    home: Some(String::from("/some/home/path")),
-                    venv: None,
-                    direnv: Some((String::from("/some/direnv"), false)),
+                    root: Some(user::Root::Sudo(String::from("user"))),
+                    ..MockEnv::default()
                 },
             )
         });
         let expected = concat!(
-            style!(fg = color!(red), bg = color!(black)),
-            " ",
-            symbol!(error),
+            style!(fg = color!(black), bg = color!(red)),
             " ",
-            style!(fg = color!(cyan), symbol!(jobs)),
+            symbol!(root),
+            " root (user) ",
+            style!(fg = color!(red), bg = color!(blue), symbol!(div)),
+            style!(fg = color!(black)),
             " ",
-            style!(fg = color!(reset), style!(fg = color!(red), "H")),
-            style!(reset to bg = color!(black)),
-            style!(fg = color!(blue)),
+            // Missing PWD
             " ",
-            symbol!(div thin),
-            " direnv ",
-            style!(fg = color!(black), bg = color!(blue), symbol!(div)),
-            style!(fg = color!(black)),
-            " ~/further/on ",
             style!(fg = color!(blue), bg = color!(reset), symbol!(div)),
             style!(fg = color!(reset)),
             " "
@@ -457,37 +729,30 @@ mod tests {
     }
 
     #[test]
-    fn direnv_active() {
+    fn remote() {
         let result = test(|s| {
             render_inner(
                 s,
-                Some(String::from("[31mH")),
-                true,
-                true,
+                None,
+                false,
+                false,
+                None,
                 &MockEnv {
-                    pwd: Some(std::path::PathBuf::from("/some/home/path/further/on")),
-                    home: Some(String::from("/some/home/path")),
-                    venv: None,
-                    direnv: Some((String::from("/some/direnv"), true)),
+                    remote: Some(String::from("user@box")),
+                    ..MockEnv::default()
                 },
             )
         });
         let expected = concat!(
-            style!(fg = color!(red), bg = color!(black)),
-            " ",
-            symbol!(error),
-            " ",
-            style!(fg = color!(cyan), symbol!(jobs)),
-            " ",
-            style!(fg = color!(reset), style!(fg = color!(red), "H")),
+            style!(fg = color!(reset), bg = color!(black)),
+            " user@box",
             style!(reset to bg = color!(black)),
-            style!(fg = color!(green)),
             " ",
-            symbol!(div thin),
-            " direnv ",
             style!(fg = color!(black), bg = color!(blue), symbol!(div)),
             style!(fg = color!(black)),
-            " ~/further/on ",
+            " ",
+            // Missing PWD
+            " ",
             style!(fg = color!(blue), bg = color!(reset), symbol!(div)),
             style!(fg = color!(reset)),
             " "
@@ -496,4 +761,720 @@ mod tests {
         println!("{expected}");
         assert_eq!(result, expected);
     }
+
+    #[test]
+    fn remote_with_host() {
+        let result = test(|s| {
+            render_inner(
+                s,
+                Some(String::from("H")),
+                false,
+                false,
+                None,
+                &MockEnv {
+                    remote: Some(String::from("user@box")),
+                    ..MockEnv::default()
+                },
+            )
+        });
+        let expected = concat!(
+            style!(fg = color!(reset), bg = color!(black)),
+            " H",
+            style!(reset to bg = color!(black)),
+            " ",
+            style!(fg = color!(black), bg = color!(blue), symbol!(div)),
+            style!(fg = color!(black)),
+            " ",
+            // Missing PWD
+            " ",
+            style!(fg = color!(blue), bg = color!(reset), symbol!(div)),
+            style!(fg = color!(reset)),
+            " "
+        );
+        println!("{result}");
+        println!("{expected}");
+        assert_eq!(result, expected);
+    }
+
+    #[test]
+    fn container() {
+        let result = test(|s| {
+            render_inner(
+                s,
+                Some(String::from("H")),
+                false,
+                false,
+                None,
+                &MockEnv {
+                    container: Some(String::from("dev-box")),
+                    ..MockEnv::default()
+                },
+            )
+        });
+        let expected = concat!(
+            style!(fg = color!(reset), bg = color!(black)),
+            " H",
+            style!(reset to bg = color!(black)),
+            " ",
+            style!(fg = color!(magenta), symbol!(container)),
+            " dev-box ",
+            style!(fg = color!(black), bg = color!(blue), symbol!(div)),
+            style!(fg = color!(black)),
+            " ",
+            // Missing PWD
+            " ",
+            style!(fg = color!(blue), bg = color!(reset), symbol!(div)),
+            style!(fg = color!(reset)),
+            " "
+        );
+        println!("{result}");
+        println!("{expected}");
+        assert_eq!(result, expected);
+    }
+
+    #[test]
+    fn nix_shell() {
+        let result = test(|s| {
+            render_inner(
+                s,
+                Some(String::from("H")),
+                false,
+                false,
+                None,
+                &MockEnv {
+                    direnv: Some((String::from("/some/direnv"), direnv::State::Loaded)),
+                    nix_shell: Some(nix::Shell {
+                        pure: false,
+                        name: Some(String::from("simpalt")),
+                    }),
+                    ..MockEnv::default()
+                },
+            )
+        });
+        let expected = concat!(
+            style!(fg = color!(reset), bg = color!(black)),
+            " H",
+            style!(reset to bg = color!(black)),
+            style!(fg = color!(green)),
+            " ",
+            symbol!(div thin),
+            " direnv",
+            style!(fg = color!(yellow)),
+            " ",
+            symbol!(div thin),
+            " ",
+            symbol!(nix),
+            " simpalt ",
+            style!(fg = color!(black), bg = color!(blue), symbol!(div)),
+            style!(fg = color!(black)),
+            " ",
+            // Missing PWD
+            " ",
+            style!(fg = color!(blue), bg = color!(reset), symbol!(div)),
+            style!(fg = color!(reset)),
+            " "
+        );
+        println!("{result}");
+        println!("{expected}");
+        assert_eq!(result, expected);
+    }
+
+    #[test]
+    fn nix_shell_pure() {
+        let result = test(|s| {
+            render_inner(
+                s,
+                Some(String::from("H")),
+                false,
+                false,
+                None,
+                &MockEnv {
+                    nix_shell: Some(nix::Shell {
+                        pure: true,
+                        name: None,
+                    }),
+                    ..MockEnv::default()
+                },
+            )
+        });
+        let expected = concat!(
+            style!(fg = color!(reset), bg = color!(black)),
+            " H",
+            style!(reset to bg = color!(black)),
+            style!(fg = color!(cyan)),
+            " ",
+            symbol!(div thin),
+            " ",
+            symbol!(nix),
+            " nix-shell ",
+            style!(fg = color!(black), bg = color!(blue), symbol!(div)),
+            style!(fg = color!(black)),
+            " ",
+            // Missing PWD
+            " ",
+            style!(fg = color!(blue), bg = color!(reset), symbol!(div)),
+            style!(fg = color!(reset)),
+            " "
+        );
+        println!("{result}");
+        println!("{expected}");
+        assert_eq!(result, expected);
+    }
+
+    #[test]
+    fn venv_version() {
+        let result = test(|s| {
+            render_inner(
+                s,
+                None,
+                false,
+                false,
+                None,
+                &MockEnv {
+                    venv: Some(python::Venv {
+                        kind: python::Kind::Virtual,
+                        path: String::from("/project/.venv"),
+                        version: Some(String::from("3.12")),
+                    }),
+                    ..MockEnv::default()
+                },
+            )
+        });
+        let expected = concat!(
+            style!(fg = color!(black), bg = color!(cyan)),
+            " .venv py 3.12 ",
+            style!(fg = color!(cyan), bg = color!(blue), symbol!(div)),
+            style!(fg = color!(black)),
+            " ",
+            // Missing PWD
+            " ",
+            style!(fg = color!(blue), bg = color!(reset), symbol!(div)),
+            style!(fg = color!(reset)),
+            " "
+        );
+        println!("{result}");
+        println!("{expected}");
+        assert_eq!(result, expected);
+    }
+
+    #[test]
+    fn conda() {
+        let result = test(|s| {
+            render_inner(
+                s,
+                None,
+                false,
+                false,
+                None,
+                &MockEnv {
+                    venv: Some(python::Venv {
+                        kind: python::Kind::Conda,
+                        path: String::from("science"),
+                        version: None,
+                    }),
+                    ..MockEnv::default()
+                },
+            )
+        });
+        let expected = concat!(
+            style!(fg = color!(black), bg = color!(cyan)),
+            " ",
+            symbol!(conda),
+            " science ",
+            style!(fg = color!(cyan), bg = color!(blue), symbol!(div)),
+            style!(fg = color!(black)),
+            " ",
+            // Missing PWD
+            " ",
+            style!(fg = color!(blue), bg = color!(reset), symbol!(div)),
+            style!(fg = color!(reset)),
+            " "
+        );
+        println!("{result}");
+        println!("{expected}");
+        assert_eq!(result, expected);
+    }
+
+    #[test]
+    fn readonly() {
+        let result = test(|s| {
+            render_inner(
+                s,
+                None,
+                false,
+                false,
+                Some(16),
+                &MockEnv {
+                    pwd: Some(std::path::PathBuf::from("/some/home/path/further/on")),
+                    home: Some(String::from("/some/home/path")),
+                    readonly: true,
+                    ..MockEnv::default()
+                },
+            )
+        });
+        let expected = concat!(
+            // Missing error
+            // Missing jobs
+            // Missing venv
+            // Missing HOST
+            style!(fg = color!(black), bg = color!(blue)),
+            " ",
+            symbol!(lock),
+            " ~/f/on ",
+            style!(fg = color!(blue), bg = color!(reset), symbol!(div)),
+            style!(fg = color!(reset)),
+            " "
+        );
+        println!("{result}");
+        println!("{expected}");
+        assert_eq!(result, expected);
+    }
+
+    #[test]
+    fn terraform() {
+        let result = test(|s| {
+            render_inner(
+                s,
+                None,
+                false,
+                false,
+                None,
+                &MockEnv {
+                    pwd: Some(std::path::PathBuf::from("/infra")),
+                    terraform: Some(String::from("default")),
+                    ..MockEnv::default()
+                },
+            )
+        });
+        let expected = concat!(
+            // Missing error
+            // Missing jobs
+            // Missing HOST
+            style!(fg = color!(magenta), bg = color!(black)),
+            " ",
+            symbol!(terraform),
+            " default ",
+            style!(fg = color!(black), bg = color!(blue), symbol!(div)),
+            style!(fg = color!(black)),
+            " /infra ",
+            style!(fg = color!(blue), bg = color!(reset), symbol!(div)),
+            style!(fg = color!(reset)),
+            " "
+        );
+        println!("{result}");
+        println!("{expected}");
+        assert_eq!(result, expected);
+    }
+
+    #[test]
+    fn terraform_workspace() {
+        let result = test(|s| {
+            render_inner(
+                s,
+                None,
+                false,
+                false,
+                None,
+                &MockEnv {
+                    pwd: Some(std::path::PathBuf::from("/infra")),
+                    terraform: Some(String::from("prod")),
+                    ..MockEnv::default()
+                },
+            )
+        });
+        let expected = concat!(
+            // Missing error
+            // Missing jobs
+            // Missing HOST
+            style!(fg = color!(black), bg = color!(yellow)),
+            " ",
+            symbol!(terraform),
+            " prod ",
+            style!(fg = color!(yellow), bg = color!(blue), symbol!(div)),
+            style!(fg = color!(black)),
+            " /infra ",
+            style!(fg = color!(blue), bg = color!(reset), symbol!(div)),
+            style!(fg = color!(reset)),
+            " "
+        );
+        println!("{result}");
+        println!("{expected}");
+        assert_eq!(result, expected);
+    }
+
+    #[test]
+    fn alias_match() {
+        let result = test(|s| {
+            render_inner(
+                s,
+                None,
+                false,
+                false,
+                None,
+                &MockEnv {
+                    pwd: Some(std::path::PathBuf::from(
+                        "/some/home/path/work/company/monorepo/crates/core",
+                    )),
+                    home: Some(String::from("/some/home/path")),
+                    aliases: vec![(
+                        String::from("@mono"),
+                        std::path::PathBuf::from("/some/home/path/work/company/monorepo"),
+                    )],
+                    ..MockEnv::default()
+                },
+            )
+        });
+        let expected = concat!(
+            // Missing error
+            // Missing jobs
+            // Missing venv
+            // Missing HOST
+            style!(fg = color!(black), bg = color!(blue)),
+            " @mono/crates/core ",
+            style!(fg = color!(blue), bg = color!(reset), symbol!(div)),
+            style!(fg = color!(reset)),
+            " "
+        );
+        println!("{result}");
+        println!("{expected}");
+        assert_eq!(result, expected);
+    }
+
+    #[test]
+    fn shortened_path() {
+        let result = test(|s| {
+            render_inner(
+                s,
+                None,
+                false,
+                false,
+                Some(20),
+                &MockEnv {
+                    pwd: Some(std::path::PathBuf::from(
+                        "/some/home/path/projects/simpalt/src",
+                    )),
+                    home: Some(String::from("/some/home/path")),
+                    ..MockEnv::default()
+                },
+            )
+        });
+        let expected = concat!(
+            // Missing error
+            // Missing jobs
+            // Missing venv
+            // Missing HOST
+            style!(fg = color!(black), bg = color!(blue)),
+            " ~/p/simpalt/src ",
+            style!(fg = color!(blue), bg = color!(reset), symbol!(div)),
+            style!(fg = color!(reset)),
+            " "
+        );
+        println!("{result}");
+        println!("{expected}");
+        assert_eq!(result, expected);
+    }
+
+    #[test]
+    fn visible_width() {
+        assert_eq!(super::visible_width(b""), 0);
+        assert_eq!(super::visible_width("[31;40m ~/ab [m".as_bytes()), 6);
+        assert_eq!(
+            super::visible_width(concat!(symbol!(div), "[38;5;23mé").as_bytes()),
+            2
+        );
+    }
+
+    #[test]
+    fn ascii_symbols() {
+        let result = test(|s| {
+            render_inner(
+                s,
+                None,
+                true,
+                false,
+                None,
+                &MockEnv {
+                    pwd: Some(std::path::PathBuf::from(concat!("/", symbol!(error)))),
+                    symbols: symbols::Set::Ascii,
+                    ..MockEnv::default()
+                },
+            )
+        });
+        let expected = concat!(
+            style!(fg = color!(red), bg = color!(black)),
+            " x ",
+            style!(fg = color!(black), bg = color!(blue), ">"),
+            style!(fg = color!(black)),
+            " /",
+            symbol!(error),
+            " ",
+            style!(fg = color!(blue), bg = color!(reset), ">"),
+            style!(fg = color!(reset)),
+            " "
+        );
+        assert_eq!(result, expected);
+    }
+
+    #[test]
+    fn all_tags() {
+        let result = test(|s| {
+            render_inner(
+                s,
+                Some(String::from("[31mH")),
+                true,
+                true,
+                None,
+                &MockEnv {
+                    pwd: Some(std::path::PathBuf::from("/some/home/path/further/on")),
+                    home: Some(String::from("/some/home/path")),
+                    venv: Some(python::Venv {
+                        kind: python::Kind::Virtual,
+                        path: String::from("py"),
+                        version: None,
+                    }),
+                    direnv: Some((String::from("/some/direnv"), direnv::State::Inactive)),
+                    ..MockEnv::default()
+                },
+            )
+        });
+        let expected = concat!(
+            style!(fg = color!(red), bg = color!(black)),
+            " ",
+            symbol!(error),
+            " ",
+            style!(fg = color!(cyan), symbol!(jobs)),
+            " ",
+            style!(fg = color!(reset), style!(fg = color!(red), "H")),
+            style!(reset to bg = color!(black)),
+            style!(fg = color!(blue)),
+            " ",
+            symbol!(div thin),
+            " direnv ",
+            style!(fg = color!(black), bg = color!(cyan), symbol!(div)),
+            style!(fg = color!(black)),
+            " py ",
+            style!(fg = color!(cyan), bg = color!(blue), symbol!(div)),
+            style!(fg = color!(black)),
+            " ~/further/on ",
+            style!(fg = color!(blue), bg = color!(reset), symbol!(div)),
+            style!(fg = color!(reset)),
+            " "
+        );
+        println!("{result}");
+        println!("{expected}");
+        assert_eq!(result, expected);
+    }
+
+    #[test]
+    fn direnv() {
+        let result = test(|s| {
+            render_inner(
+                s,
+                Some(String::from("[31mH")),
+                true,
+                true,
+                None,
+                &MockEnv {
+                    pwd: Some(std::path::PathBuf::from("/some/home/path/further/on")),
+                    home: Some(String::from("/some/home/path")),
+                    venv: None,
+                    direnv: Some((String::from("/some/direnv"), direnv::State::Inactive)),
+                    ..MockEnv::default()
+                },
+            )
+        });
+        let expected = concat!(
+            style!(fg = color!(red), bg = color!(black)),
+            " ",
+            symbol!(error),
+            " ",
+            style!(fg = color!(cyan), symbol!(jobs)),
+            " ",
+            style!(fg = color!(reset), style!(fg = color!(red), "H")),
+            style!(reset to bg = color!(black)),
+            style!(fg = color!(blue)),
+            " ",
+            symbol!(div thin),
+            " direnv ",
+            style!(fg = color!(black), bg = color!(blue), symbol!(div)),
+            style!(fg = color!(black)),
+            " ~/further/on ",
+            style!(fg = color!(blue), bg = color!(reset), symbol!(div)),
+            style!(fg = color!(reset)),
+            " "
+        );
+        println!("{result}");
+        println!("{expected}");
+        assert_eq!(result, expected);
+    }
+
+    #[test]
+    fn direnv_blocked() {
+        let result = test(|s| {
+            render_inner(
+                s,
+                Some(String::from("[31mH")),
+                true,
+                true,
+                None,
+                &MockEnv {
+                    pwd: Some(std::path::PathBuf::from("/some/home/path/further/on")),
+                    home: Some(String::from("/some/home/path")),
+                    venv: None,
+                    direnv: Some((String::from("/some/direnv"), direnv::State::Blocked)),
+                    ..MockEnv::default()
+                },
+            )
+        });
+        let expected = concat!(
+            style!(fg = color!(red), bg = color!(black)),
+            " ",
+            symbol!(error),
+            " ",
+            style!(fg = color!(cyan), symbol!(jobs)),
+            " ",
+            style!(fg = color!(reset), style!(fg = color!(red), "H")),
+            style!(reset to bg = color!(black)),
+            style!(fg = color!(red)),
+            " ",
+            symbol!(div thin),
+            " direnv ",
+            style!(fg = color!(black), bg = color!(blue), symbol!(div)),
+            style!(fg = color!(black)),
+            " ~/further/on ",
+            style!(fg = color!(blue), bg = color!(reset), symbol!(div)),
+            style!(fg = color!(reset)),
+            " "
+        );
+        println!("{result}");
+        println!("{expected}");
+        assert_eq!(result, expected);
+    }
+
+    #[test]
+    fn direnv_active() {
+        let result = test(|s| {
+            render_inner(
+                s,
+                Some(String::from("[31mH")),
+                true,
+                true,
+                None,
+                &MockEnv {
+                    pwd: Some(std::path::PathBuf::from("/some/home/path/further/on")),
+                    home: Some(String::from("/some/home/path")),
+                    venv: None,
+                    direnv: Some((String::from("/some/direnv"), direnv::State::Loaded)),
+                    ..MockEnv::default()
+                },
+            )
+        });
+        let expected = concat!(
+            style!(fg = color!(red), bg = color!(black)),
+            " ",
+            symbol!(error),
+            " ",
+            style!(fg = color!(cyan), symbol!(jobs)),
+            " ",
+            style!(fg = color!(reset), style!(fg = color!(red), "H")),
+            style!(reset to bg = color!(black)),
+            style!(fg = color!(green)),
+            " ",
+            symbol!(div thin),
+            " direnv ",
+            style!(fg = color!(black), bg = color!(blue), symbol!(div)),
+            style!(fg = color!(black)),
+            " ~/further/on ",
+            style!(fg = color!(blue), bg = color!(reset), symbol!(div)),
+            style!(fg = color!(reset)),
+            " "
+        );
+        println!("{result}");
+        println!("{expected}");
+        assert_eq!(result, expected);
+    }
+
+    #[test]
+    fn direnv_count() {
+        let result = test(|s| {
+            render_inner(
+                s,
+                Some(String::from("[31mH")),
+                true,
+                true,
+                None,
+                &MockEnv {
+                    pwd: Some(std::path::PathBuf::from("/some/home/path/further/on")),
+                    home: Some(String::from("/some/home/path")),
+                    venv: None,
+                    direnv: Some((String::from("/some/direnv"), direnv::State::Loaded)),
+                    direnv_count: Some(3),
+                    ..MockEnv::default()
+                },
+            )
+        });
+        let expected = concat!(
+            style!(fg = color!(red), bg = color!(black)),
+            " ",
+            symbol!(error),
+            " ",
+            style!(fg = color!(cyan), symbol!(jobs)),
+            " ",
+            style!(fg = color!(reset), style!(fg = color!(red), "H")),
+            style!(reset to bg = color!(black)),
+            style!(fg = color!(green)),
+            " ",
+            symbol!(div thin),
+            " direnv (3) ",
+            style!(fg = color!(black), bg = color!(blue), symbol!(div)),
+            style!(fg = color!(black)),
+            " ~/further/on ",
+            style!(fg = color!(blue), bg = color!(reset), symbol!(div)),
+            style!(fg = color!(reset)),
+            " "
+        );
+        println!("{result}");
+        println!("{expected}");
+        assert_eq!(result, expected);
+    }
+
+    #[test]
+    fn solarized_base() {
+        let result = test(|s| {
+            let out = crate::compat::Downgrade::new(s, crate::palette::Depth::Base);
+            render_inner(
+                crate::compat::Theme::new(out, crate::palette::Palette::Solarized),
+                Some(String::from("H")),
+                true,
+                true,
+                None,
+                &MockEnv {
+                    pwd: Some(std::path::PathBuf::from("/some/path")),
+                    venv: Some(python::Venv {
+                        kind: python::Kind::Virtual,
+                        path: String::from("/project/.venv"),
+                        version: None,
+                    }),
+                    ..MockEnv::default()
+                },
+            )
+        });
+        println!("{result}");
+
+        // Each segment keeps its text readable: the fg and bg of a style never collapse together
+        let mut styles = 0;
+        for sequence in result.split('\x1b').filter_map(|s| s.strip_prefix('[')) {
+            let params = sequence.split_once('m').map_or("", |(params, _)| params);
+            let layer = |prefix| {
+                params
+                    .split(';')
+                    .find_map(|param: &str| param.strip_prefix(prefix).filter(|c| c.len() == 1))
+            };
+            if let (Some(fg), Some(bg)) = (layer('3'), layer('4')) {
+                assert_ne!(fg, bg, "{sequence:?}");
+                styles += 1;
+            }
+        }
+        assert!(styles > 0);
+    }
 }
@@ -1,6 +1,12 @@
-mod direnv;
+mod container;
+pub(super) mod direnv;
 mod long;
+mod nix;
+mod path;
+pub(super) mod python;
 mod short;
+mod terraform;
+pub(super) mod user;
 
 use super::Compat;
 use crate::{Result, compat};
@@ -11,6 +17,8 @@ pub struct Args {
     pub error: bool,
     pub jobs: bool,
     pub long: bool,
+    pub columns: Option<usize>,
+    pub percent: usize,
     pub compat: Compat,
 }
 
@@ -18,32 +26,73 @@ pub fn render<Out>(out: Out, args: Args) -> Result
 where
     Out: std::io::Write,
 {
+    let max_width = max_width(args.columns, args.percent);
+
     match args.compat {
-        Compat::None => render_inner(out, args.long, args.host, args.error, args.jobs),
+        Compat::None => render_inner(
+            compat::Plain::from_env(compat::Theme::from_env(compat::Downgrade::from_env(out))),
+            args.long,
+            args.host,
+            args.error,
+            args.jobs,
+            max_width,
+        ),
         Compat::Zsh => render_inner(
-            compat::Zsh::new(out),
+            compat::Plain::from_env(compat::Theme::from_env(compat::Downgrade::from_env(
+                compat::Zsh::new(out),
+            ))),
             args.long,
             args.host,
             args.error,
             args.jobs,
+            max_width,
         ),
         Compat::Win(sub) => render_inner(
-            compat::Win::new(out, sub),
+            compat::Plain::from_env(compat::Win::new(
+                compat::Theme::from_env(compat::Downgrade::from_env(out)),
+                sub,
+            )),
             args.long,
             args.host,
             args.error,
             args.jobs,
+            max_width,
         ),
     }
 }
 
-fn render_inner<Out>(out: Out, long: bool, host: Option<String>, error: bool, jobs: bool) -> Result
+fn render_inner<Out>(
+    out: Out,
+    long: bool,
+    host: Option<String>,
+    error: bool,
+    jobs: bool,
+    max_width: Option<usize>,
+) -> Result
 where
     Out: std::io::Write,
 {
     if long {
-        long::render(out, host, error, jobs)
+        long::render(out, host, error, jobs, max_width)
     } else {
         short::render(out, host, error, jobs)
     }
 }
+
+fn max_width(columns: Option<usize>, percent: usize) -> Option<usize> {
+    columns.map(|columns| columns.saturating_mul(percent.min(100)) / 100)
+}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn max_width() {
+        assert_eq!(super::max_width(None, 50), None);
+        assert_eq!(super::max_width(Some(120), 30), Some(36));
+        assert_eq!(super::max_width(Some(80), 250), Some(80));
+        assert_eq!(
+            super::max_width(Some(usize::MAX), 50),
+            Some(usize::MAX / 100)
+        );
+    }
+}
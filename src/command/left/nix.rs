@@ -0,0 +1,16 @@
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Shell {
+    pub pure: bool,
+    pub name: Option<String>,
+}
+
+pub fn shell() -> Option<Shell> {
+    let state = std::env::var("IN_NIX_SHELL")
+        .ok()
+        .filter(|state| !state.is_empty())?;
+
+    Some(Shell {
+        pure: state == "pure",
+        name: std::env::var("name").ok().filter(|name| !name.is_empty()),
+    })
+}
@@ -0,0 +1,243 @@
+const ELLIPSIS: &str = "…";
+
+pub fn aliases() -> Vec<(String, std::path::PathBuf)> {
+    std::env::var_os("SIMPALT_ALIASES")
+        .map(|aliases| parse_aliases(&aliases))
+        .unwrap_or_default()
+}
+
+fn parse_aliases(aliases: &std::ffi::OsStr) -> Vec<(String, std::path::PathBuf)> {
+    std::env::split_paths(aliases)
+        .filter_map(|entry| {
+            let (alias, path) = entry.to_str()?.split_once('=')?;
+            (!alias.is_empty() && !path.is_empty())
+                .then(|| (String::from(alias), std::path::PathBuf::from(path)))
+        })
+        .collect()
+}
+
+#[cfg(unix)]
+pub fn writable(path: &std::path::Path) -> bool {
+    let Ok(path) = std::ffi::CString::new(std::os::unix::ffi::OsStrExt::as_bytes(path.as_os_str()))
+    else {
+        return true;
+    };
+    if unsafe { libc::access(path.as_ptr(), libc::W_OK) } == 0 {
+        return true;
+    }
+
+    // Only a denied write is worth a lock, not e.g. a directory that was removed
+    !matches!(
+        std::io::Error::last_os_error().raw_os_error(),
+        Some(libc::EACCES | libc::EROFS)
+    )
+}
+
+#[cfg(not(unix))]
+pub fn writable(path: &std::path::Path) -> bool {
+    std::fs::metadata(path).map_or(true, |meta| !meta.permissions().readonly())
+}
+
+pub fn substitute<'a>(
+    path: &'a std::path::Path,
+    aliases: &'a [(String, std::path::PathBuf)],
+) -> Option<(&'a str, &'a std::path::Path)> {
+    aliases
+        .iter()
+        .filter_map(|(alias, prefix)| {
+            path.strip_prefix(prefix)
+                .ok()
+                .map(|rest| (alias.as_str(), rest, prefix.components().count()))
+        })
+        .max_by_key(|(_, _, depth)| *depth)
+        .map(|(alias, rest, _)| (alias, rest))
+}
+
+pub fn shorten(path: &str, max: usize) -> std::borrow::Cow<'_, str> {
+    if path.chars().count() <= max {
+        return std::borrow::Cow::Borrowed(path);
+    }
+
+    let parts = path.split(std::path::MAIN_SEPARATOR).collect::<Vec<_>>();
+    let [head, middle @ .., leaf] = parts.as_slice() else {
+        return std::borrow::Cow::Borrowed(path);
+    };
+
+    if middle.is_empty() {
+        return std::borrow::Cow::Borrowed(path);
+    }
+
+    let mut middle = middle.to_vec();
+    let mut candidate = String::from(path);
+
+    for i in 0..middle.len() {
+        middle[i] = abbreviate(middle[i]);
+        candidate = join(head, &middle, leaf);
+        if candidate.chars().count() <= max {
+            return std::borrow::Cow::Owned(candidate);
+        }
+    }
+
+    for i in 1..=middle.len() {
+        let mut collapsed = Vec::with_capacity(middle.len() - i + 1);
+        collapsed.push(ELLIPSIS);
+        collapsed.extend_from_slice(&middle[i..]);
+        candidate = join(head, &collapsed, leaf);
+        if candidate.chars().count() <= max {
+            break;
+        }
+    }
+
+    std::borrow::Cow::Owned(candidate)
+}
+
+fn abbreviate(component: &str) -> &str {
+    let skip = usize::from(component.starts_with('.'));
+    component
+        .char_indices()
+        .nth(skip + 1)
+        .map_or(component, |(i, _)| &component[..i])
+}
+
+fn join(head: &str, middle: &[&str], leaf: &str) -> String {
+    let mut joined = String::from(head);
+    for component in middle.iter().chain(std::iter::once(&leaf)) {
+        joined.push(std::path::MAIN_SEPARATOR);
+        joined.push_str(component);
+    }
+    joined
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn path(parts: &[&str]) -> String {
+        parts.join(std::path::MAIN_SEPARATOR_STR)
+    }
+
+    fn aliases(aliases: &[(&str, &str)]) -> Vec<(String, std::path::PathBuf)> {
+        aliases
+            .iter()
+            .map(|(alias, path)| (String::from(*alias), std::path::PathBuf::from(path)))
+            .collect()
+    }
+
+    #[test]
+    fn parse() {
+        let input = std::env::join_paths([
+            "@mono=/work/company/monorepo",
+            "=/nothing",
+            "@empty=",
+            "no_equals",
+            "@eq=/with=equals",
+        ])
+        .unwrap();
+        assert_eq!(
+            parse_aliases(&input),
+            aliases(&[("@mono", "/work/company/monorepo"), ("@eq", "/with=equals"),])
+        );
+    }
+
+    #[test]
+    fn substitute_longest() {
+        let aliases = aliases(&[
+            ("~", "/home/user"),
+            ("@mono", "/home/user/work/monorepo"),
+            ("@work", "/home/user/work"),
+        ]);
+
+        assert_eq!(
+            substitute(
+                std::path::Path::new("/home/user/work/monorepo/src"),
+                &aliases
+            ),
+            Some(("@mono", std::path::Path::new("src")))
+        );
+        assert_eq!(
+            substitute(std::path::Path::new("/home/user/work/other"), &aliases),
+            Some(("@work", std::path::Path::new("other")))
+        );
+        assert_eq!(
+            substitute(std::path::Path::new("/home/user/work"), &aliases),
+            Some(("@work", std::path::Path::new("")))
+        );
+        assert_eq!(
+            substitute(std::path::Path::new("/home/user"), &aliases),
+            Some(("~", std::path::Path::new("")))
+        );
+    }
+
+    #[test]
+    fn substitute_whole_components() {
+        let aliases = aliases(&[("@mono", "/work/mono")]);
+
+        assert_eq!(
+            substitute(std::path::Path::new("/work/monorepo"), &aliases),
+            None
+        );
+        assert_eq!(substitute(std::path::Path::new("/"), &aliases), None);
+    }
+
+    #[test]
+    fn writable_dir() {
+        assert!(writable(&std::env::temp_dir()));
+        assert!(writable(std::path::Path::new("/some/missing/path")));
+    }
+
+    #[test]
+    fn fits() {
+        let input = path(&["~", "projects", "simpalt"]);
+        assert_eq!(shorten(&input, 100), input);
+        assert_eq!(shorten(&input, input.len()), input);
+    }
+
+    #[test]
+    fn nothing_to_shorten() {
+        let input = path(&["~", "projects"]);
+        assert_eq!(shorten(&input, 1), input);
+
+        let input = path(&["", ""]);
+        assert_eq!(shorten(&input, 0), input);
+    }
+
+    #[test]
+    fn abbreviate_from_the_left() {
+        let input = path(&["~", "projects", "simpalt", "src", "leaf"]);
+        assert_eq!(
+            shorten(&input, 20),
+            path(&["~", "p", "simpalt", "src", "leaf"])
+        );
+        assert_eq!(shorten(&input, 14), path(&["~", "p", "s", "src", "leaf"]));
+        assert_eq!(shorten(&input, 12), path(&["~", "p", "s", "s", "leaf"]));
+    }
+
+    #[test]
+    fn abbreviate_hidden() {
+        let input = path(&["~", ".config", "nvim", "lua"]);
+        assert_eq!(shorten(&input, 13), path(&["~", ".c", "nvim", "lua"]));
+        assert_eq!(shorten(&input, 12), path(&["~", ".c", "n", "lua"]));
+    }
+
+    #[test]
+    fn abbreviate_unicode() {
+        let input = path(&["", "über", "straße", "leaf"]);
+        assert_eq!(shorten(&input, 14), path(&["", "ü", "straße", "leaf"]));
+    }
+
+    #[test]
+    fn ellipsis() {
+        let input = path(&["~", "projects", "simpalt", "src", "leaf"]);
+        assert_eq!(shorten(&input, 11), path(&["~", "…", "s", "leaf"]));
+        assert_eq!(shorten(&input, 9), path(&["~", "…", "leaf"]));
+        assert_eq!(shorten(&input, 7), path(&["~", "…", "leaf"]));
+        assert_eq!(shorten(&input, 1), path(&["~", "…", "leaf"]));
+    }
+
+    #[test]
+    fn root() {
+        let input = path(&["", "usr", "local", "share", "leaf"]);
+        assert_eq!(shorten(&input, 12), path(&["", "u", "l", "s", "leaf"]));
+        assert_eq!(shorten(&input, 1), path(&["", "…", "leaf"]));
+    }
+}
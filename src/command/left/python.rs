@@ -0,0 +1,241 @@
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum Kind {
+    Virtual,
+    Conda,
+}
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Venv {
+    pub kind: Kind,
+    pub path: String,
+    pub version: Option<String>,
+}
+
+impl Venv {
+    pub fn name(&self) -> &str {
+        self.path
+            .rsplit(std::path::MAIN_SEPARATOR)
+            .next()
+            .unwrap_or(&self.path)
+    }
+
+    pub fn with_version(self) -> Self {
+        if self.kind != Kind::Virtual {
+            return self;
+        }
+
+        let version = std::fs::read_to_string(std::path::Path::new(&self.path).join("pyvenv.cfg"))
+            .ok()
+            .and_then(|cfg| parse_version(&cfg));
+
+        Self { version, ..self }
+    }
+}
+
+pub fn venv() -> Option<Venv> {
+    detect(|var| std::env::var(var).ok())
+}
+
+fn detect<F>(var: F) -> Option<Venv>
+where
+    F: Fn(&str) -> Option<String>,
+{
+    let var = |name| var(name).filter(|value| !value.is_empty());
+
+    if let Some(path) = var("VIRTUAL_ENV") {
+        return Some(Venv {
+            kind: Kind::Virtual,
+            path,
+            version: None,
+        });
+    }
+
+    let path = var("CONDA_DEFAULT_ENV").or_else(|| var("CONDA_PREFIX"))?;
+    let root = var("CONDA_ROOT")
+        .or_else(|| var("_CONDA_ROOT"))
+        .or_else(|| var("MAMBA_ROOT_PREFIX"));
+
+    // The base environment is the one installed at the root prefix, whatever it is named
+    let base = match (var("CONDA_PREFIX"), root) {
+        (Some(prefix), Some(root)) => std::path::Path::new(&prefix) == std::path::Path::new(&root),
+        _ => path == "base",
+    };
+    if base && var("SIMPALT_CONDA_BASE").is_none() {
+        return None;
+    }
+
+    Some(Venv {
+        kind: Kind::Conda,
+        path,
+        version: None,
+    })
+}
+
+fn parse_version(cfg: &str) -> Option<String> {
+    let version = cfg.lines().find_map(|line| {
+        let (key, value) = line.split_once('=')?;
+        matches!(key.trim(), "version" | "version_info").then(|| value.trim())
+    })?;
+
+    let mut parts = version.split('.');
+    let major = parts.next().filter(|major| !major.is_empty())?;
+    Some(match parts.next() {
+        Some(minor) => format!("{major}.{minor}"),
+        None => String::from(major),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn vars<'a>(vars: &'a [(&str, &str)]) -> impl Fn(&str) -> Option<String> + 'a {
+        |var| {
+            vars.iter()
+                .find(|(name, _)| *name == var)
+                .map(|(_, value)| String::from(*value))
+        }
+    }
+
+    #[test]
+    fn none() {
+        assert_eq!(detect(vars(&[])), None);
+        assert_eq!(detect(vars(&[("VIRTUAL_ENV", "")])), None);
+    }
+
+    #[test]
+    fn virtual_env() {
+        assert_eq!(
+            detect(vars(&[
+                ("VIRTUAL_ENV", "/project/.venv"),
+                ("CONDA_DEFAULT_ENV", "science"),
+            ])),
+            Some(Venv {
+                kind: Kind::Virtual,
+                path: String::from("/project/.venv"),
+                version: None,
+            })
+        );
+    }
+
+    #[test]
+    fn conda() {
+        assert_eq!(
+            detect(vars(&[
+                ("CONDA_DEFAULT_ENV", "science"),
+                ("CONDA_PREFIX", "/opt/conda/envs/science"),
+            ])),
+            Some(Venv {
+                kind: Kind::Conda,
+                path: String::from("science"),
+                version: None,
+            })
+        );
+        assert_eq!(
+            detect(vars(&[("CONDA_PREFIX", "/opt/conda/envs/science")])),
+            Some(Venv {
+                kind: Kind::Conda,
+                path: String::from("/opt/conda/envs/science"),
+                version: None,
+            })
+        );
+    }
+
+    #[test]
+    fn conda_base() {
+        assert_eq!(detect(vars(&[("CONDA_DEFAULT_ENV", "base")])), None);
+        assert_eq!(
+            detect(vars(&[
+                ("CONDA_DEFAULT_ENV", "base"),
+                ("SIMPALT_CONDA_BASE", "1"),
+            ])),
+            Some(Venv {
+                kind: Kind::Conda,
+                path: String::from("base"),
+                version: None,
+            })
+        );
+    }
+
+    #[test]
+    fn conda_root_prefix() {
+        assert_eq!(
+            detect(vars(&[
+                ("CONDA_DEFAULT_ENV", "miniforge3"),
+                ("CONDA_PREFIX", "/opt/conda/"),
+                ("_CONDA_ROOT", "/opt/conda"),
+            ])),
+            None
+        );
+        assert_eq!(
+            detect(vars(&[
+                ("CONDA_PREFIX", "/home/user/micromamba"),
+                ("MAMBA_ROOT_PREFIX", "/home/user/micromamba"),
+            ])),
+            None
+        );
+        assert_eq!(
+            detect(vars(&[
+                ("CONDA_DEFAULT_ENV", "base"),
+                ("CONDA_PREFIX", "/opt/conda/envs/base"),
+                ("CONDA_ROOT", "/opt/conda"),
+            ])),
+            Some(Venv {
+                kind: Kind::Conda,
+                path: String::from("base"),
+                version: None,
+            })
+        );
+    }
+
+    #[test]
+    fn name() {
+        let venv = Venv {
+            kind: Kind::Virtual,
+            path: ["", "project", ".venv"].join(std::path::MAIN_SEPARATOR_STR),
+            version: None,
+        };
+        assert_eq!(venv.name(), ".venv");
+    }
+
+    #[test]
+    fn version() {
+        assert_eq!(
+            parse_version(
+                "home = /usr/bin\ninclude-system-site-packages = false\nversion = 3.12.1\n"
+            ),
+            Some(String::from("3.12"))
+        );
+        assert_eq!(
+            parse_version(
+                "home = /usr/bin\nimplementation = CPython\nversion_info = 3.11.9.final.0\n"
+            ),
+            Some(String::from("3.11"))
+        );
+        assert_eq!(parse_version("version=3"), Some(String::from("3")));
+        assert_eq!(parse_version("home = /usr/bin\n"), None);
+        assert_eq!(parse_version("version = \n"), None);
+    }
+
+    #[test]
+    fn with_version() {
+        let fixture = crate::Fixture::new("python-venv", &[("pyvenv.cfg", "version = 3.13.0\n")]);
+        let root = &fixture.0;
+
+        let venv = Venv {
+            kind: Kind::Virtual,
+            path: String::from(root.to_str().unwrap()),
+            version: None,
+        }
+        .with_version();
+        let conda = Venv {
+            kind: Kind::Conda,
+            path: String::from(root.to_str().unwrap()),
+            version: None,
+        }
+        .with_version();
+
+        assert_eq!(venv.version.as_deref(), Some("3.13"));
+        assert_eq!(conda.version, None);
+    }
+}
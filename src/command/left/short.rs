@@ -1,11 +1,12 @@
-use crate::Result;
+use super::{direnv, python};
 use crate::git::short as git;
+use crate::{Result, symbols};
 
 macro_rules! chevron {
     ($color: expr) => {
         concat!(
-            style!(fg = color!(black), bg = $color, symbol!(div)),
-            style!(reset to fg = $color, symbol!(div)),
+            style!(fg = color!(black), bg = $color, "{div}"),
+            style!(reset to fg = $color, "{div}"),
         )
     };
 }
@@ -28,46 +29,100 @@ where
     Out: std::io::Write,
     Env: EnvFetcher,
 {
+    let symbols = enver.symbols();
     write!(out, style!(reset to bg = color!(black), " "))?;
     let mut should_recolor = false;
 
     if error {
-        write!(out, style!(fg = color!(red), symbol!(error), " "))?;
+        write!(
+            out,
+            style!(fg = color!(red), "{} "),
+            symbols.get(symbol!(error))
+        )?;
         should_recolor = true;
     }
 
     if jobs {
-        write!(out, style!(fg = color!(cyan), symbol!(jobs), " "))?;
+        write!(
+            out,
+            style!(fg = color!(cyan), "{} "),
+            symbols.get(symbol!(jobs))
+        )?;
         should_recolor = true;
     }
 
-    if let Some(active) = enver.direnv() {
-        if active {
-            write!(out, style!(fg = color!(green), symbol!(direnv), " "))?;
+    let pwd = enver.pwd();
+
+    if let Some(state) = enver.direnv(pwd.as_deref()) {
+        let color = match state {
+            direnv::State::Loaded => style!(fg = color!(green)),
+            direnv::State::Inactive => style!(fg = color!(blue)),
+            direnv::State::Stale => style!(fg = color!(yellow)),
+            direnv::State::Blocked => style!(fg = color!(red)),
+        };
+        write!(out, "{color}{} ", symbols.get(symbol!(direnv)))?;
+        should_recolor = true;
+    }
+
+    if let Some(pure) = enver.nix_shell() {
+        let color = if pure {
+            style!(fg = color!(cyan))
         } else {
-            write!(out, style!(fg = color!(blue), symbol!(direnv), " "))?;
-        }
+            style!(fg = color!(yellow))
+        };
+        write!(out, "{color}{} ", symbols.get(symbol!(nix)))?;
         should_recolor = true;
     }
 
-    if enver.venv() {
-        write!(out, style!(fg = color!(green), symbol!(python), " "))?;
+    if let Some(venv) = enver.venv() {
+        let venv = match venv {
+            python::Kind::Virtual => symbol!(python),
+            python::Kind::Conda => symbol!(conda),
+        };
+        write!(out, style!(fg = color!(green), "{} "), symbols.get(venv))?;
         should_recolor = true;
     }
 
-    if let Some(host) = host {
-        if should_recolor {
+    if enver.container() {
+        write!(
+            out,
+            style!(fg = color!(magenta), "{} "),
+            symbols.get(symbol!(container))
+        )?;
+        should_recolor = true;
+    }
+
+    let root = enver.root();
+
+    if let Some(host) = host.or_else(|| enver.remote()) {
+        if root {
+            write!(out, style!(fg = color!(red), "{host}"), host = host)?;
+        } else if should_recolor {
             write!(out, style!(fg = color!(reset), "{host}"), host = host)?;
-            should_recolor = false;
         } else {
             write!(out, "{host}")?;
         }
         write!(out, style!(reset to bg = color!(black), " "))?;
+        should_recolor = false;
+    } else if root {
+        write!(
+            out,
+            style!(fg = color!(red), "{} "),
+            symbols.get(symbol!(root))
+        )?;
+        should_recolor = true;
     }
 
-    let pwd = enver.pwd();
-
     if let Some(ref pwd) = pwd {
+        if !enver.writable(pwd) {
+            write!(
+                out,
+                style!(fg = color!(red), "{} "),
+                symbols.get(symbol!(lock))
+            )?;
+            should_recolor = true;
+        }
+
         if should_recolor {
             write!(
                 out,
@@ -80,9 +135,9 @@ where
     }
 
     if let Some(ref pwd) = pwd {
-        out.git(git::parse(pwd))?;
+        out.git(symbols, git::parse(pwd))?;
     } else {
-        write!(out, chevron!(color!(blue)))?;
+        write!(out, chevron!(color!(blue)), div = symbols.get(symbol!(div)))?;
     }
 
     write!(out, style!(reset, " "))?;
@@ -96,6 +151,14 @@ fn pwd_string(path: &std::path::PathBuf, enver: &impl EnvFetcher) -> String {
         return String::from("~");
     }
 
+    if let Some((alias, _)) = enver
+        .aliases()
+        .into_iter()
+        .find(|(_, prefix)| prefix.eq(path))
+    {
+        return alias;
+    }
+
     let (prefix, components) =
         path.components()
             .fold((None, vec![]), |(prefix, mut list), curr| match curr {
@@ -121,26 +184,41 @@ fn pwd_string(path: &std::path::PathBuf, enver: &impl EnvFetcher) -> String {
 }
 
 trait Writer {
-    fn git(&mut self, repo: git::Repo) -> Result;
+    fn git(&mut self, symbols: symbols::Set, repo: git::Repo) -> Result;
 }
 
 impl<W: std::io::Write> Writer for W {
-    fn git(&mut self, repo: git::Repo) -> Result {
+    fn git(&mut self, symbols: symbols::Set, repo: git::Repo) -> Result {
+        let div = symbols.get(symbol!(div));
+        let branch = symbols.get(symbol!(branch));
+
         macro_rules! branch {
             (none $color: expr) => {
-                write!(self, chevron!($color))
+                write!(self, chevron!($color), div = div)
             };
             (warn $color: expr) => {
-                write!(self, concat!(symbol!(warn), chevron!($color)))
+                write!(
+                    self,
+                    concat!("{warn}", chevron!($color)),
+                    warn = symbols.get(symbol!(warn)),
+                    div = div
+                )
             };
             ($branch: expr, $color: expr) => {
                 write!(
                     self,
-                    style!(fg = $branch, symbol!(branch), chevron!($color))
+                    style!(fg = $branch, "{branch}", chevron!($color)),
+                    branch = branch,
+                    div = div
                 )
             };
             ($color: expr) => {
-                write!(self, concat!(symbol!(branch), chevron!($color)))
+                write!(
+                    self,
+                    concat!("{branch}", chevron!($color)),
+                    branch = branch,
+                    div = div
+                )
             };
         }
 
@@ -171,8 +249,15 @@ impl<W: std::io::Write> Writer for W {
 trait EnvFetcher {
     fn pwd(&self) -> Option<std::path::PathBuf>;
     fn home(&self) -> Option<std::path::PathBuf>;
-    fn venv(&self) -> bool;
-    fn direnv(&self) -> Option<bool>;
+    fn remote(&self) -> Option<String>;
+    fn root(&self) -> bool;
+    fn aliases(&self) -> Vec<(String, std::path::PathBuf)>;
+    fn writable(&self, path: &std::path::Path) -> bool;
+    fn venv(&self) -> Option<python::Kind>;
+    fn container(&self) -> bool;
+    fn direnv(&self, pwd: Option<&std::path::Path>) -> Option<direnv::State>;
+    fn nix_shell(&self) -> Option<bool>;
+    fn symbols(&self) -> symbols::Set;
 }
 
 #[derive(Copy, Clone)]
@@ -189,12 +274,40 @@ impl EnvFetcher for SysEnv {
         std::env::var_os("HOME").map(std::path::PathBuf::from)
     }
 
-    fn venv(&self) -> bool {
-        std::env::var("VIRTUAL_ENV").is_ok()
+    fn remote(&self) -> Option<String> {
+        super::user::remote()
+    }
+
+    fn root(&self) -> bool {
+        super::user::root().is_some()
+    }
+
+    fn aliases(&self) -> Vec<(String, std::path::PathBuf)> {
+        super::path::aliases()
+    }
+
+    fn writable(&self, path: &std::path::Path) -> bool {
+        super::path::writable(path)
     }
 
-    fn direnv(&self) -> Option<bool> {
-        super::direnv::is_active()
+    fn venv(&self) -> Option<python::Kind> {
+        python::venv().map(|venv| venv.kind)
+    }
+
+    fn container(&self) -> bool {
+        super::container::detect().is_some()
+    }
+
+    fn direnv(&self, pwd: Option<&std::path::Path>) -> Option<direnv::State> {
+        direnv::detect(pwd).map(|(_, state)| state)
+    }
+
+    fn nix_shell(&self) -> Option<bool> {
+        super::nix::shell().map(|shell| shell.pure)
+    }
+
+    fn symbols(&self) -> symbols::Set {
+        symbols::Set::from_env()
     }
 }
 
@@ -203,6 +316,15 @@ mod tests {
     use super::*;
     use crate::test;
 
+    macro_rules! chevron {
+        ($color: expr) => {
+            concat!(
+                style!(fg = color!(black), bg = $color, symbol!(div)),
+                style!(reset to fg = $color, symbol!(div)),
+            )
+        };
+    }
+
     macro_rules! branch {
         () => {
             symbol!(branch)
@@ -213,11 +335,19 @@ mod tests {
     }
 
     #[derive(Default)]
+    #[allow(clippy::struct_excessive_bools)]
     struct MockEnv {
         pwd: Option<std::path::PathBuf>,
         home: Option<std::path::PathBuf>,
-        venv: bool,
-        direnv: Option<bool>,
+        remote: Option<String>,
+        root: bool,
+        aliases: Vec<(String, std::path::PathBuf)>,
+        readonly: bool,
+        venv: Option<python::Kind>,
+        container: bool,
+        direnv: Option<direnv::State>,
+        nix_shell: Option<bool>,
+        symbols: symbols::Set,
     }
 
     impl EnvFetcher for MockEnv {
@@ -229,13 +359,41 @@ mod tests {
             self.home.clone()
         }
 
-        fn venv(&self) -> bool {
+        fn remote(&self) -> Option<String> {
+            self.remote.clone()
+        }
+
+        fn root(&self) -> bool {
+            self.root
+        }
+
+        fn aliases(&self) -> Vec<(String, std::path::PathBuf)> {
+            self.aliases.clone()
+        }
+
+        fn writable(&self, _: &std::path::Path) -> bool {
+            !self.readonly
+        }
+
+        fn venv(&self) -> Option<python::Kind> {
             self.venv
         }
 
-        fn direnv(&self) -> Option<bool> {
+        fn container(&self) -> bool {
+            self.container
+        }
+
+        fn direnv(&self, _: Option<&std::path::Path>) -> Option<direnv::State> {
             self.direnv
         }
+
+        fn nix_shell(&self) -> Option<bool> {
+            self.nix_shell
+        }
+
+        fn symbols(&self) -> symbols::Set {
+            self.symbols
+        }
     }
 
     #[test]
@@ -292,6 +450,40 @@ mod tests {
         assert_eq!(result, expected);
     }
 
+    #[test]
+    fn ascii_symbols() {
+        let result = test(|s| {
+            render_inner(
+                s,
+                None,
+                true,
+                true,
+                &MockEnv {
+                    pwd: Some(std::path::PathBuf::from("/")),
+                    symbols: symbols::Set::Ascii,
+                    ..MockEnv::default()
+                },
+            )
+        });
+        let expected = concat!(
+            style!(reset to bg = color!(black)),
+            " ",
+            style!(fg = color!(red)),
+            "x ",
+            style!(fg = color!(cyan)),
+            "& ",
+            style!(fg = color!(reset)),
+            "/ ",
+            style!(fg = color!(black), bg = color!(blue)),
+            ">",
+            style!(reset to fg = color!(blue)),
+            ">",
+            style!(reset),
+            " "
+        );
+        assert_eq!(result, expected);
+    }
+
     #[test]
     fn last_path() {
         let result = test(|s| {
@@ -358,6 +550,307 @@ mod tests {
         assert_eq!(result, expected);
     }
 
+    #[test]
+    fn root_host() {
+        let result = test(|s| {
+            render_inner(
+                s,
+                Some(String::from("H")),
+                true,
+                false,
+                &MockEnv {
+                    root: true,
+                    ..MockEnv::default()
+                },
+            )
+        });
+        let expected = concat!(
+            style!(reset to bg = color!(black)),
+            " ",
+            style!(fg = color!(red), symbol!(error)),
+            " ",
+            style!(fg = color!(red), "H"),
+            style!(reset to bg = color!(black)),
+            " ",
+            // Missing PWD
+            chevron!(color!(blue)),
+            style!(reset),
+            " "
+        );
+        println!("{result}");
+        println!("{expected}");
+        assert_eq!(result, expected);
+    }
+
+    #[test]
+    fn root_no_host() {
+        let result = test(|s| {
+            render_inner(
+                s,
+                None,
+                false,
+                false,
+                &MockEnv {
+                    pwd: Some(std::path::PathBuf::from("/")),
+                    root: true,
+                    ..MockEnv::default()
+                },
+            )
+        });
+        let expected = concat!(
+            style!(reset to bg = color!(black)),
+            " ",
+            style!(fg = color!(red), symbol!(root)),
+            " ",
+            style!(fg = color!(reset)),
+            "/",
+            " ",
+            chevron!(color!(blue)),
+            style!(reset),
+            " "
+        );
+        println!("{result}");
+        println!("{expected}");
+        assert_eq!(result, expected);
+    }
+
+    #[test]
+    fn remote() {
+        let result = test(|s| {
+            render_inner(
+                s,
+                None,
+                false,
+                false,
+                &MockEnv {
+                    pwd: Some(std::path::PathBuf::from("/")),
+                    remote: Some(String::from("user@box")),
+                    ..MockEnv::default()
+                },
+            )
+        });
+        let expected = concat!(
+            style!(reset to bg = color!(black)),
+            " ",
+            // Missing statuses
+            "user@box",
+            style!(reset to bg = color!(black)),
+            " ",
+            "/",
+            " ",
+            chevron!(color!(blue)),
+            style!(reset),
+            " "
+        );
+        println!("{result}");
+        println!("{expected}");
+        assert_eq!(result, expected);
+    }
+
+    #[test]
+    fn container() {
+        let result = test(|s| {
+            render_inner(
+                s,
+                None,
+                false,
+                false,
+                &MockEnv {
+                    pwd: Some(std::path::PathBuf::from("/")),
+                    container: true,
+                    ..MockEnv::default()
+                },
+            )
+        });
+        let expected = concat!(
+            style!(reset to bg = color!(black)),
+            " ",
+            style!(fg = color!(magenta), symbol!(container)),
+            " ",
+            style!(fg = color!(reset)),
+            "/",
+            " ",
+            chevron!(color!(blue)),
+            style!(reset),
+            " "
+        );
+        println!("{result}");
+        println!("{expected}");
+        assert_eq!(result, expected);
+    }
+
+    #[test]
+    fn nix_shell() {
+        let result = test(|s| {
+            render_inner(
+                s,
+                None,
+                false,
+                false,
+                &MockEnv {
+                    pwd: Some(std::path::PathBuf::from("/")),
+                    direnv: Some(direnv::State::Loaded),
+                    nix_shell: Some(false),
+                    ..MockEnv::default()
+                },
+            )
+        });
+        let expected = concat!(
+            style!(reset to bg = color!(black)),
+            " ",
+            style!(fg = color!(green), symbol!(direnv)),
+            " ",
+            style!(fg = color!(yellow), symbol!(nix)),
+            " ",
+            style!(fg = color!(reset)),
+            "/",
+            " ",
+            chevron!(color!(blue)),
+            style!(reset),
+            " "
+        );
+        println!("{result}");
+        println!("{expected}");
+        assert_eq!(result, expected);
+    }
+
+    #[test]
+    fn conda() {
+        let result = test(|s| {
+            render_inner(
+                s,
+                None,
+                false,
+                false,
+                &MockEnv {
+                    pwd: Some(std::path::PathBuf::from("/")),
+                    venv: Some(python::Kind::Conda),
+                    ..MockEnv::default()
+                },
+            )
+        });
+        let expected = concat!(
+            style!(reset to bg = color!(black)),
+            " ",
+            style!(fg = color!(green), symbol!(conda)),
+            " ",
+            style!(fg = color!(reset)),
+            "/",
+            " ",
+            chevron!(color!(blue)),
+            style!(reset),
+            " "
+        );
+        println!("{result}");
+        println!("{expected}");
+        assert_eq!(result, expected);
+    }
+
+    #[test]
+    fn readonly() {
+        let result = test(|s| {
+            render_inner(
+                s,
+                Some(String::from("H")),
+                false,
+                false,
+                &MockEnv {
+                    pwd: Some(std::path::PathBuf::from("/some/home/path/")),
+                    readonly: true,
+                    ..MockEnv::default()
+                },
+            )
+        });
+        let expected = concat!(
+            style!(reset to bg = color!(black)),
+            " ",
+            // Missing statuses
+            "H",
+            style!(reset to bg = color!(black)),
+            " ",
+            style!(fg = color!(red), symbol!(lock)),
+            " ",
+            style!(fg = color!(reset)),
+            "path",
+            " ",
+            chevron!(color!(blue)),
+            style!(reset),
+            " "
+        );
+        println!("{result}");
+        println!("{expected}");
+        assert_eq!(result, expected);
+    }
+
+    #[test]
+    fn alias_match() {
+        let result = test(|s| {
+            render_inner(
+                s,
+                None,
+                false,
+                false,
+                &MockEnv {
+                    pwd: Some(std::path::PathBuf::from("/work/company/monorepo/")),
+                    home: Some(std::path::PathBuf::from("/some/home/path")),
+                    aliases: vec![(
+                        String::from("@mono"),
+                        std::path::PathBuf::from("/work/company/monorepo"),
+                    )],
+                    ..MockEnv::default()
+                },
+            )
+        });
+        let expected = concat!(
+            style!(reset to bg = color!(black)),
+            " ",
+            // Missing statuses
+            // Missing HOST
+            "@mono",
+            " ",
+            chevron!(color!(blue)),
+            style!(reset),
+            " "
+        );
+        println!("{result}");
+        println!("{expected}");
+        assert_eq!(result, expected);
+    }
+
+    #[test]
+    fn alias_leaf() {
+        let result = test(|s| {
+            render_inner(
+                s,
+                None,
+                false,
+                false,
+                &MockEnv {
+                    pwd: Some(std::path::PathBuf::from("/work/company/monorepo/src")),
+                    aliases: vec![(
+                        String::from("@mono"),
+                        std::path::PathBuf::from("/work/company/monorepo"),
+                    )],
+                    ..MockEnv::default()
+                },
+            )
+        });
+        let expected = concat!(
+            style!(reset to bg = color!(black)),
+            " ",
+            // Missing statuses
+            // Missing HOST
+            "src",
+            " ",
+            chevron!(color!(blue)),
+            style!(reset),
+            " "
+        );
+        println!("{result}");
+        println!("{expected}");
+        assert_eq!(result, expected);
+    }
+
     #[test]
     fn all_tags() {
         let result = test(|s| {
@@ -369,8 +862,9 @@ mod tests {
                 &MockEnv {
                     pwd: Some(std::path::PathBuf::from("/some/home/path/")),
                     home: Some(std::path::PathBuf::from("/some/home/path")),
-                    venv: true,
-                    direnv: Some(false),
+                    venv: Some(python::Kind::Virtual),
+                    direnv: Some(direnv::State::Inactive),
+                    ..MockEnv::default()
                 },
             )
         });
@@ -411,8 +905,9 @@ mod tests {
                 &MockEnv {
                     pwd: Some(std::path::PathBuf::from("/some/home/path/")),
                     home: Some(std::path::PathBuf::from("/some/home/path")),
-                    venv: false,
-                    direnv: Some(false),
+                    venv: None,
+                    direnv: Some(direnv::State::Inactive),
+                    ..MockEnv::default()
                 },
             )
         });
@@ -440,6 +935,47 @@ mod tests {
         assert_eq!(result, expected);
     }
 
+    #[test]
+    fn direnv_stale() {
+        let result = test(|s| {
+            render_inner(
+                s,
+                Some(String::from("[31mH")),
+                true,
+                true,
+                &MockEnv {
+                    pwd: Some(std::path::PathBuf::from("/some/home/path/")),
+                    home: Some(std::path::PathBuf::from("/some/home/path")),
+                    venv: None,
+                    direnv: Some(direnv::State::Stale),
+                    ..MockEnv::default()
+                },
+            )
+        });
+        let expected = concat!(
+            style!(reset to bg = color!(black)),
+            " ",
+            style!(fg = color!(red), symbol!(error)),
+            " ",
+            style!(fg = color!(cyan), symbol!(jobs)),
+            " ",
+            style!(fg = color!(yellow), symbol!(direnv)),
+            " ",
+            style!(fg = color!(reset)),
+            style!(fg = color!(red), "H"),
+            style!(reset to bg = color!(black)),
+            " ",
+            "~",
+            " ",
+            chevron!(color!(blue)),
+            style!(reset),
+            " "
+        );
+        println!("{result}");
+        println!("{expected}");
+        assert_eq!(result, expected);
+    }
+
     #[test]
     fn direnv_active() {
         let result = test(|s| {
@@ -451,8 +987,9 @@ mod tests {
                 &MockEnv {
                     pwd: Some(std::path::PathBuf::from("/some/home/path/")),
                     home: Some(std::path::PathBuf::from("/some/home/path")),
-                    venv: false,
-                    direnv: Some(true),
+                    venv: None,
+                    direnv: Some(direnv::State::Loaded),
+                    ..MockEnv::default()
                 },
             )
         });
@@ -483,23 +1020,23 @@ mod tests {
     #[test]
     fn git_sync_clean() {
         assert_eq!(
-            test(|s| s.git(git::Repo::Clean(git::Sync::Behind))),
+            test(|s| s.git(symbols::Set::Nerd, git::Repo::Clean(git::Sync::Behind))),
             concat!(branch!(color!(red)), chevron!(color!(green)))
         );
         assert_eq!(
-            test(|s| s.git(git::Repo::Clean(git::Sync::Ahead))),
+            test(|s| s.git(symbols::Set::Nerd, git::Repo::Clean(git::Sync::Ahead))),
             concat!(branch!(color!(yellow)), chevron!(color!(green)))
         );
         assert_eq!(
-            test(|s| s.git(git::Repo::Clean(git::Sync::Diverged))),
+            test(|s| s.git(symbols::Set::Nerd, git::Repo::Clean(git::Sync::Diverged))),
             concat!(branch!(color!(magenta)), chevron!(color!(green)))
         );
         assert_eq!(
-            test(|s| s.git(git::Repo::Clean(git::Sync::UpToDate))),
+            test(|s| s.git(symbols::Set::Nerd, git::Repo::Clean(git::Sync::UpToDate))),
             concat!(branch!(), chevron!(color!(green)))
         );
         assert_eq!(
-            test(|s| s.git(git::Repo::Clean(git::Sync::Local))),
+            test(|s| s.git(symbols::Set::Nerd, git::Repo::Clean(git::Sync::Local))),
             concat!(branch!(color!(blue)), chevron!(color!(green)))
         );
     }
@@ -507,50 +1044,56 @@ mod tests {
     #[test]
     fn git_sync_dirty() {
         assert_eq!(
-            test(|s| s.git(git::Repo::Dirty(git::Sync::Behind))),
+            test(|s| s.git(symbols::Set::Nerd, git::Repo::Dirty(git::Sync::Behind))),
             concat!(branch!(color!(red)), chevron!(color!(yellow)))
         );
         assert_eq!(
-            test(|s| s.git(git::Repo::Dirty(git::Sync::Ahead))),
+            test(|s| s.git(symbols::Set::Nerd, git::Repo::Dirty(git::Sync::Ahead))),
             concat!(branch!(color!(yellow)), chevron!(color!(yellow)))
         );
         assert_eq!(
-            test(|s| s.git(git::Repo::Dirty(git::Sync::Diverged))),
+            test(|s| s.git(symbols::Set::Nerd, git::Repo::Dirty(git::Sync::Diverged))),
             concat!(branch!(color!(magenta)), chevron!(color!(yellow)))
         );
         assert_eq!(
-            test(|s| s.git(git::Repo::Dirty(git::Sync::UpToDate))),
+            test(|s| s.git(symbols::Set::Nerd, git::Repo::Dirty(git::Sync::UpToDate))),
             concat!(branch!(), chevron!(color!(yellow)))
         );
         assert_eq!(
-            test(|s| s.git(git::Repo::Dirty(git::Sync::Local))),
+            test(|s| s.git(symbols::Set::Nerd, git::Repo::Dirty(git::Sync::Local))),
             concat!(branch!(color!(blue)), chevron!(color!(yellow)))
         );
     }
 
     #[test]
     fn git_status() {
-        assert_eq!(test(|s| s.git(git::Repo::None)), chevron!(color!(blue)));
         assert_eq!(
-            test(|s| s.git(git::Repo::Clean(git::Sync::UpToDate))),
+            test(|s| s.git(symbols::Set::Nerd, git::Repo::None)),
+            chevron!(color!(blue))
+        );
+        assert_eq!(
+            test(|s| s.git(symbols::Set::Nerd, git::Repo::Clean(git::Sync::UpToDate))),
             concat!(branch!(), chevron!(color!(green)))
         );
         assert_eq!(
-            test(|s| s.git(git::Repo::Dirty(git::Sync::UpToDate))),
+            test(|s| s.git(symbols::Set::Nerd, git::Repo::Dirty(git::Sync::UpToDate))),
             concat!(branch!(), chevron!(color!(yellow)))
         );
         assert_eq!(
-            test(|s| s.git(git::Repo::Detached)),
+            test(|s| s.git(symbols::Set::Nerd, git::Repo::Detached)),
             concat!(branch!(), chevron!(color!(magenta)))
         );
         assert_eq!(
-            test(|s| s.git(git::Repo::Pending)),
+            test(|s| s.git(symbols::Set::Nerd, git::Repo::Pending)),
             concat!(symbol!(warn), chevron!(color!(cyan)))
         );
         assert_eq!(
-            test(|s| s.git(git::Repo::Untracked)),
+            test(|s| s.git(symbols::Set::Nerd, git::Repo::Untracked)),
             concat!(branch!(), chevron!(color!(cyan)))
         );
-        assert_eq!(test(|s| s.git(git::Repo::Error)), chevron!(color!(red)));
+        assert_eq!(
+            test(|s| s.git(symbols::Set::Nerd, git::Repo::Error)),
+            chevron!(color!(red))
+        );
     }
 }
@@ -0,0 +1,67 @@
+pub fn workspace(pwd: &std::path::Path) -> Option<String> {
+    workspace_in(pwd, |var| std::env::var(var).ok())
+}
+
+fn workspace_in<F>(pwd: &std::path::Path, var: F) -> Option<String>
+where
+    F: Fn(&str) -> Option<String>,
+{
+    // Both terraform and OpenTofu keep the selected workspace in the same file
+    let environment = std::fs::read_to_string(pwd.join(".terraform").join("environment")).ok()?;
+
+    var("TF_WORKSPACE")
+        .filter(|workspace| !workspace.is_empty())
+        .or_else(|| Some(String::from(environment.trim())))
+        .filter(|workspace| !workspace.is_empty())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Fixture;
+
+    fn fixture(name: &str, environment: Option<&str>) -> Fixture {
+        let name = format!("terraform-{name}");
+        match environment {
+            Some(environment) => Fixture::new(&name, &[(".terraform/environment", environment)]),
+            None => Fixture::new(&name, &[(".terraform/", "")]),
+        }
+    }
+
+    fn no_var(_: &str) -> Option<String> {
+        None
+    }
+
+    #[test]
+    fn selected() {
+        let fixture = fixture("selected", Some("staging\n"));
+        assert_eq!(
+            workspace_in(&fixture.0, no_var),
+            Some(String::from("staging"))
+        );
+    }
+
+    #[test]
+    fn not_initialized() {
+        let fixture = fixture("uninit", None);
+        assert_eq!(workspace_in(&fixture.0, no_var), None);
+        assert_eq!(
+            workspace_in(&fixture.0, |_| Some(String::from("prod"))),
+            None
+        );
+    }
+
+    #[test]
+    fn env_override() {
+        let fixture = fixture("override", Some("default"));
+        assert_eq!(
+            workspace_in(&fixture.0, |var| (var == "TF_WORKSPACE")
+                .then(|| String::from("prod"))),
+            Some(String::from("prod"))
+        );
+        assert_eq!(
+            workspace_in(&fixture.0, |_| Some(String::new())),
+            Some(String::from("default"))
+        );
+    }
+}
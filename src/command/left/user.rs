@@ -0,0 +1,69 @@
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum Root {
+    Login,
+    Sudo(String),
+}
+
+pub fn root() -> Option<Root> {
+    if !is_root() {
+        return None;
+    }
+
+    Some(
+        std::env::var("SUDO_USER")
+            .ok()
+            .filter(|user| !user.is_empty())
+            .map_or(Root::Login, Root::Sudo),
+    )
+}
+
+#[cfg(unix)]
+fn is_root() -> bool {
+    unsafe { libc::geteuid() == 0 }
+}
+
+#[cfg(not(unix))]
+fn is_root() -> bool {
+    false
+}
+
+pub fn remote() -> Option<String> {
+    if std::env::var_os("SSH_CONNECTION").is_none() && std::env::var_os("SSH_TTY").is_none() {
+        return None;
+    }
+
+    std::env::var("SIMPALT_REMOTE_HOST")
+        .ok()
+        .filter(|host| !host.is_empty())
+        .or_else(|| {
+            let host = hostname()?;
+            Some(
+                match std::env::var("USER").or_else(|_| std::env::var("USERNAME")) {
+                    Ok(user) => format!("{user}@{host}"),
+                    Err(_) => host,
+                },
+            )
+        })
+}
+
+#[cfg(unix)]
+pub fn hostname() -> Option<String> {
+    let mut buffer = [0_u8; 256];
+    if unsafe { libc::gethostname(buffer.as_mut_ptr().cast(), buffer.len()) } != 0 {
+        return None;
+    }
+
+    std::ffi::CStr::from_bytes_until_nul(&buffer)
+        .ok()?
+        .to_str()
+        .ok()?
+        .split('.')
+        .next()
+        .filter(|host| !host.is_empty())
+        .map(String::from)
+}
+
+#[cfg(not(unix))]
+pub fn hostname() -> Option<String> {
+    std::env::var("COMPUTERNAME").ok()
+}
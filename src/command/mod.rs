@@ -1,4 +1,6 @@
+mod direnv;
 mod help;
+mod json;
 mod left;
 mod right;
 mod tmux;
@@ -6,14 +8,18 @@ mod version;
 
 pub use help::Args as Help;
 pub use left::Args as Left;
-pub use right::Args as Right;
-pub use tmux::Args as Tmux;
+pub use right::{Args as Right, Segment};
+pub use tmux::{Args as Tmux, Left as TmuxLeft, Segment as TmuxSegment, Window as TmuxWindow};
 
 #[derive(Debug, Eq, PartialEq)]
 pub enum Command {
     Right(Right),
     Left(Left),
     Tmux(Tmux),
+    TmuxLeft(TmuxLeft),
+    TmuxWindow(TmuxWindow),
+    Direnv,
+    Json,
     Version,
     Help(Help),
 }
@@ -27,6 +33,10 @@ impl Command {
             Self::Right(args) => right::render(out, args),
             Self::Left(args) => left::render(out, args),
             Self::Tmux(args) => tmux::render(out, args),
+            Self::TmuxLeft(args) => tmux::render_left(out, &args),
+            Self::TmuxWindow(args) => tmux::render_window(out, &args),
+            Self::Direnv => direnv::render(out),
+            Self::Json => json::render(out),
             Self::Version => version::render(out),
             Self::Help(args) => help::render(out, args),
         }
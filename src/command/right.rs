@@ -1,10 +1,22 @@
 use super::Compat;
-use crate::{Result, compat};
+use crate::{Result, battery, cloud, compat, docker, kube, symbols, toolchain};
 use chrono::Timelike;
 
 #[derive(Debug, Eq, PartialEq)]
 pub struct Args {
     pub compat: Compat,
+    pub segments: Vec<Segment>,
+}
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum Segment {
+    Kube,
+    Docker,
+    Aws,
+    Gcp,
+    Azure,
+    Toolchain,
+    Battery,
 }
 
 pub fn render<Out>(out: Out, args: Args) -> Result
@@ -12,16 +24,40 @@ where
     Out: std::io::Write,
 {
     match args.compat {
-        Compat::None => render_inner(out),
-        Compat::Zsh => render_inner(compat::Zsh::new(out)),
-        Compat::Win(sub) => render_inner(compat::Win::new(out, sub)),
+        Compat::None => render_inner(
+            compat::Plain::from_env(compat::Theme::from_env(compat::Downgrade::from_env(out))),
+            &args.segments,
+            &SysEnv,
+        ),
+        Compat::Zsh => render_inner(
+            compat::Plain::from_env(compat::Theme::from_env(compat::Downgrade::from_env(
+                compat::Zsh::new(out),
+            ))),
+            &args.segments,
+            &SysEnv,
+        ),
+        Compat::Win(sub) => render_inner(
+            compat::Plain::from_env(compat::Win::new(
+                compat::Theme::from_env(compat::Downgrade::from_env(out)),
+                sub,
+            )),
+            &args.segments,
+            &SysEnv,
+        ),
     }
 }
 
-fn render_inner<Out>(mut out: Out) -> Result
+fn render_inner<Out, Env>(mut out: Out, segments: &[Segment], enver: &Env) -> Result
 where
     Out: std::io::Write,
+    Env: EnvFetcher,
 {
+    let symbols = enver.symbols();
+
+    for segment in segments {
+        render_segment(&mut out, *segment, symbols, enver)?;
+    }
+
     let time = chrono::DateTime::<chrono::Local>::from(std::time::SystemTime::now());
 
     write!(
@@ -34,17 +70,530 @@ where
     out.flush()
 }
 
+fn render_segment<Out, Env>(
+    out: &mut Out,
+    segment: Segment,
+    symbols: symbols::Set,
+    enver: &Env,
+) -> Result
+where
+    Out: std::io::Write,
+    Env: EnvFetcher,
+{
+    match segment {
+        Segment::Kube => {
+            if let Some(kube) = enver.kube() {
+                if kube.production {
+                    write!(out, style!(fg = color!(red)))?;
+                } else {
+                    write!(out, style!(fg = color!(blue)))?;
+                }
+                write!(out, "{} {}", symbols.get(symbol!(kube)), kube.name)?;
+                if let Some(namespace) = kube.namespace {
+                    write!(out, "/{namespace}")?;
+                }
+                write!(out, " ")?;
+            }
+        }
+        Segment::Docker => {
+            if let Some(docker) = enver.docker() {
+                write!(
+                    out,
+                    style!(fg = color!(blue), "{symbol} {docker} "),
+                    symbol = symbols.get(symbol!(docker)),
+                    docker = docker
+                )?;
+            }
+        }
+        Segment::Aws => {
+            if let Some(aws) = enver.aws() {
+                write!(
+                    out,
+                    style!(fg = color!(yellow), "{}"),
+                    symbols.get(symbol!(aws))
+                )?;
+                if let Some(profile) = aws.profile {
+                    write!(out, " {profile}")?;
+                }
+                if let Some(region) = aws.region {
+                    write!(out, " ({region})")?;
+                }
+                write!(out, " ")?;
+            }
+        }
+        Segment::Gcp => {
+            if let Some(gcp) = enver.gcp() {
+                write!(
+                    out,
+                    style!(fg = color!(cyan), "{symbol} {gcp} "),
+                    symbol = symbols.get(symbol!(gcp)),
+                    gcp = gcp
+                )?;
+            }
+        }
+        Segment::Azure => {
+            if let Some(azure) = enver.azure() {
+                write!(
+                    out,
+                    style!(fg = color!(magenta), "{symbol} {azure} "),
+                    symbol = symbols.get(symbol!(azure)),
+                    azure = azure
+                )?;
+            }
+        }
+        Segment::Toolchain => {
+            if let Some(toolchain) = enver.toolchain() {
+                let (color, tool) = match &toolchain.tool {
+                    toolchain::Tool::Rust => (color!(red), symbols.get(symbol!(rust))),
+                    toolchain::Tool::Node => (color!(green), symbols.get(symbol!(node))),
+                    toolchain::Tool::Go => (color!(cyan), symbols.get(symbol!(go))),
+                    toolchain::Tool::Python => (color!(yellow), symbols.get(symbol!(python))),
+                    toolchain::Tool::Other(tool) => (color!(white), tool.as_str()),
+                };
+                write!(
+                    out,
+                    style!(fg = "{color}", "{tool} {version} "),
+                    color = color,
+                    tool = tool,
+                    version = toolchain.version
+                )?;
+            }
+        }
+        Segment::Battery => {
+            if let Some(battery) = enver.battery() {
+                let (color, symbol) = if battery.low {
+                    (color!(red), symbol!(battery low))
+                } else {
+                    (color!(yellow), symbol!(battery))
+                };
+                write!(
+                    out,
+                    style!(fg = "{color}", "{symbol} {capacity}% "),
+                    color = color,
+                    symbol = symbols.get(symbol),
+                    capacity = battery.capacity
+                )?;
+            }
+        }
+    }
+    Ok(())
+}
+
+trait EnvFetcher {
+    fn kube(&self) -> Option<kube::Context>;
+    fn docker(&self) -> Option<String>;
+    fn aws(&self) -> Option<cloud::Aws>;
+    fn gcp(&self) -> Option<String>;
+    fn azure(&self) -> Option<String>;
+    fn toolchain(&self) -> Option<toolchain::Toolchain>;
+    fn battery(&self) -> Option<battery::Battery>;
+    fn symbols(&self) -> symbols::Set;
+}
+
+#[derive(Copy, Clone)]
+struct SysEnv;
+
+impl EnvFetcher for SysEnv {
+    fn kube(&self) -> Option<kube::Context> {
+        kube::current()
+    }
+
+    fn docker(&self) -> Option<String> {
+        docker::context()
+    }
+
+    fn aws(&self) -> Option<cloud::Aws> {
+        cloud::aws()
+    }
+
+    fn gcp(&self) -> Option<String> {
+        cloud::gcp()
+    }
+
+    fn azure(&self) -> Option<String> {
+        cloud::azure()
+    }
+
+    fn toolchain(&self) -> Option<toolchain::Toolchain> {
+        std::env::current_dir()
+            .ok()
+            .and_then(|pwd| toolchain::detect(&pwd))
+    }
+
+    fn battery(&self) -> Option<battery::Battery> {
+        battery::status()
+    }
+
+    fn symbols(&self) -> symbols::Set {
+        symbols::Set::from_env()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::test;
 
+    #[derive(Default)]
+    struct MockEnv {
+        kube: Option<kube::Context>,
+        docker: Option<String>,
+        aws: Option<cloud::Aws>,
+        gcp: Option<String>,
+        azure: Option<String>,
+        toolchain: Option<toolchain::Toolchain>,
+        battery: Option<battery::Battery>,
+        symbols: symbols::Set,
+    }
+
+    impl EnvFetcher for MockEnv {
+        fn kube(&self) -> Option<kube::Context> {
+            self.kube.clone()
+        }
+
+        fn docker(&self) -> Option<String> {
+            self.docker.clone()
+        }
+
+        fn aws(&self) -> Option<cloud::Aws> {
+            self.aws.clone()
+        }
+
+        fn gcp(&self) -> Option<String> {
+            self.gcp.clone()
+        }
+
+        fn azure(&self) -> Option<String> {
+            self.azure.clone()
+        }
+
+        fn toolchain(&self) -> Option<toolchain::Toolchain> {
+            self.toolchain.clone()
+        }
+
+        fn battery(&self) -> Option<battery::Battery> {
+            self.battery
+        }
+
+        fn symbols(&self) -> symbols::Set {
+            self.symbols
+        }
+    }
+
     #[test]
     fn right() {
-        let result = test(|s| render_inner(s));
+        let result = test(|s| render_inner(s, &[], &MockEnv::default()));
 
         let regex =
             regex::Regex::new("^\\[38;5;23m[0-2][0-9]:[0-5][0-9]:[0-5][0-9]\\[m$").unwrap();
         assert!(regex.is_match(&result));
     }
+
+    #[test]
+    fn disabled_segments() {
+        let result = test(|s| {
+            render_inner(
+                s,
+                &[],
+                &MockEnv {
+                    kube: Some(kube::Context {
+                        name: String::from("local"),
+                        namespace: None,
+                        production: false,
+                    }),
+                    gcp: Some(String::from("default")),
+                    ..MockEnv::default()
+                },
+            )
+        });
+
+        let regex =
+            regex::Regex::new("^\\[38;5;23m[0-2][0-9]:[0-5][0-9]:[0-5][0-9]\\[m$").unwrap();
+        assert!(regex.is_match(&result));
+    }
+
+    #[test]
+    fn kube() {
+        let result = test(|s| {
+            render_inner(
+                s,
+                &[Segment::Kube],
+                &MockEnv {
+                    kube: Some(kube::Context {
+                        name: String::from("local"),
+                        namespace: Some(String::from("default")),
+                        production: false,
+                    }),
+                    ..MockEnv::default()
+                },
+            )
+        });
+
+        let regex = regex::Regex::new(concat!(
+            "^\\[34m",
+            symbol!(kube),
+            " local/default \\[38;5;23m[0-2][0-9]:[0-5][0-9]:[0-5][0-9]\\[m$"
+        ))
+        .unwrap();
+        assert!(regex.is_match(&result));
+    }
+
+    #[test]
+    fn kube_production() {
+        let result = test(|s| {
+            render_inner(
+                s,
+                &[Segment::Kube],
+                &MockEnv {
+                    kube: Some(kube::Context {
+                        name: String::from("prod"),
+                        namespace: None,
+                        production: true,
+                    }),
+                    ..MockEnv::default()
+                },
+            )
+        });
+
+        let regex = regex::Regex::new(concat!(
+            "^\\[31m",
+            symbol!(kube),
+            " prod \\[38;5;23m[0-2][0-9]:[0-5][0-9]:[0-5][0-9]\\[m$"
+        ))
+        .unwrap();
+        assert!(regex.is_match(&result));
+    }
+
+    #[test]
+    fn cloud() {
+        let result = test(|s| {
+            render_inner(
+                s,
+                &[Segment::Azure, Segment::Aws, Segment::Gcp, Segment::Kube],
+                &MockEnv {
+                    aws: Some(cloud::Aws {
+                        profile: Some(String::from("dev")),
+                        region: Some(String::from("eu-west-1")),
+                    }),
+                    gcp: Some(String::from("default")),
+                    azure: Some(String::from("Production")),
+                    ..MockEnv::default()
+                },
+            )
+        });
+
+        let regex = regex::Regex::new(concat!(
+            "^\\[35m",
+            symbol!(azure),
+            " Production \\[33m",
+            symbol!(aws),
+            " dev \\(eu-west-1\\) \\[36m",
+            symbol!(gcp),
+            " default \\[38;5;23m[0-2][0-9]:[0-5][0-9]:[0-5][0-9]\\[m$"
+        ))
+        .unwrap();
+        assert!(regex.is_match(&result));
+    }
+
+    #[test]
+    fn aws_region_only() {
+        let result = test(|s| {
+            render_inner(
+                s,
+                &[Segment::Aws],
+                &MockEnv {
+                    aws: Some(cloud::Aws {
+                        profile: None,
+                        region: Some(String::from("us-east-1")),
+                    }),
+                    ..MockEnv::default()
+                },
+            )
+        });
+
+        let regex = regex::Regex::new(concat!(
+            "^\\[33m",
+            symbol!(aws),
+            " \\(us-east-1\\) \\[38;5;23m[0-2][0-9]:[0-5][0-9]:[0-5][0-9]\\[m$"
+        ))
+        .unwrap();
+        assert!(regex.is_match(&result));
+    }
+
+    #[test]
+    fn toolchain() {
+        let result = test(|s| {
+            render_inner(
+                s,
+                &[Segment::Toolchain],
+                &MockEnv {
+                    toolchain: Some(toolchain::Toolchain {
+                        tool: toolchain::Tool::Rust,
+                        version: String::from("1.85.0"),
+                    }),
+                    ..MockEnv::default()
+                },
+            )
+        });
+
+        let regex = regex::Regex::new(concat!(
+            "^\\[31m",
+            symbol!(rust),
+            " 1\\.85\\.0 \\[38;5;23m[0-2][0-9]:[0-5][0-9]:[0-5][0-9]\\[m$"
+        ))
+        .unwrap();
+        assert!(regex.is_match(&result));
+    }
+
+    #[test]
+    fn toolchain_other() {
+        let result = test(|s| {
+            render_inner(
+                s,
+                &[Segment::Toolchain],
+                &MockEnv {
+                    toolchain: Some(toolchain::Toolchain {
+                        tool: toolchain::Tool::Other(String::from("terraform")),
+                        version: String::from("1.7.0"),
+                    }),
+                    ..MockEnv::default()
+                },
+            )
+        });
+
+        let regex = regex::Regex::new(
+            "^\\[37mterraform 1\\.7\\.0 \\[38;5;23m[0-2][0-9]:[0-5][0-9]:[0-5][0-9]\\[m$",
+        )
+        .unwrap();
+        assert!(regex.is_match(&result));
+    }
+
+    #[test]
+    fn docker() {
+        let result = test(|s| {
+            render_inner(
+                s,
+                &[Segment::Kube, Segment::Docker],
+                &MockEnv {
+                    kube: Some(kube::Context {
+                        name: String::from("local"),
+                        namespace: None,
+                        production: false,
+                    }),
+                    docker: Some(String::from("colima")),
+                    ..MockEnv::default()
+                },
+            )
+        });
+
+        let regex = regex::Regex::new(concat!(
+            "^\\[34m",
+            symbol!(kube),
+            " local \\[34m",
+            symbol!(docker),
+            " colima \\[38;5;23m[0-2][0-9]:[0-5][0-9]:[0-5][0-9]\\[m$"
+        ))
+        .unwrap();
+        assert!(regex.is_match(&result));
+    }
+
+    #[test]
+    fn battery() {
+        let result = test(|s| {
+            render_inner(
+                s,
+                &[Segment::Battery],
+                &MockEnv {
+                    battery: Some(battery::Battery {
+                        capacity: 64,
+                        discharging: true,
+                        low: false,
+                    }),
+                    ..MockEnv::default()
+                },
+            )
+        });
+
+        let regex = regex::Regex::new(concat!(
+            "^\\[33m",
+            symbol!(battery),
+            " 64% \\[38;5;23m[0-2][0-9]:[0-5][0-9]:[0-5][0-9]\\[m$"
+        ))
+        .unwrap();
+        assert!(regex.is_match(&result));
+    }
+
+    #[test]
+    fn battery_low() {
+        let result = test(|s| {
+            render_inner(
+                s,
+                &[Segment::Battery],
+                &MockEnv {
+                    battery: Some(battery::Battery {
+                        capacity: 9,
+                        discharging: false,
+                        low: true,
+                    }),
+                    ..MockEnv::default()
+                },
+            )
+        });
+
+        let regex = regex::Regex::new(concat!(
+            "^\\[31m",
+            symbol!(battery low),
+            " 9% \\[38;5;23m[0-2][0-9]:[0-5][0-9]:[0-5][0-9]\\[m$"
+        ))
+        .unwrap();
+        assert!(regex.is_match(&result));
+    }
+
+    #[test]
+    fn battery_zsh() {
+        let result = test(|s| {
+            render_inner(
+                compat::Zsh::new(s),
+                &[Segment::Battery],
+                &MockEnv {
+                    battery: Some(battery::Battery {
+                        capacity: 64,
+                        discharging: true,
+                        low: false,
+                    }),
+                    ..MockEnv::default()
+                },
+            )
+        });
+
+        let regex = regex::Regex::new(concat!(
+            "^%\\{\\[33m%\\}",
+            symbol!(battery),
+            " 64%% %\\{\\[38;5;23m%\\}[0-2][0-9]:[0-5][0-9]:[0-5][0-9]%\\{\\[m%\\}$"
+        ))
+        .unwrap();
+        assert!(regex.is_match(&result));
+    }
+
+    #[test]
+    fn unicode_symbols() {
+        let result = test(|s| {
+            render_inner(
+                s,
+                &[Segment::Docker],
+                &MockEnv {
+                    docker: Some(String::from("remote")),
+                    symbols: symbols::Set::Unicode,
+                    ..MockEnv::default()
+                },
+            )
+        });
+
+        let regex = regex::Regex::new(concat!(
+            "^\\[34m▣ remote ",
+            "\\[38;5;23m[0-2][0-9]:[0-5][0-9]:[0-5][0-9]\\[m$"
+        ))
+        .unwrap();
+        assert!(regex.is_match(&result));
+    }
 }
@@ -1,5 +1,8 @@
-use crate::Result;
+use super::left::user;
 use crate::git::long as git;
+use crate::{Result, compat, palette, symbols, system};
+use chrono::Timelike;
+use std::io::Write;
 
 macro_rules! style {
     (reset $(, $($param: expr),*)?) => {
@@ -64,71 +67,417 @@ macro_rules! color {
 #[derive(Debug, Eq, PartialEq)]
 pub struct Args {
     pub pwd: String,
+    pub segments: Vec<Segment>,
+    pub session: Option<String>,
+    pub host: bool,
+    pub clock: bool,
+}
+
+#[derive(Debug, Eq, PartialEq)]
+pub struct Left {
+    pub session: Option<String>,
+    pub host: bool,
+}
+
+#[derive(Debug, Eq, PartialEq)]
+pub struct Window {
+    pub index: String,
+    pub name: String,
+    pub active: bool,
+    pub path: String,
+}
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum Segment {
+    Load,
+    Memory,
 }
 
 pub fn render<Out>(out: Out, args: Args) -> Result
 where
     Out: std::io::Write,
 {
-    render_git(out, git::parse(&std::path::PathBuf::from(args.pwd)))
+    let mut out = compat::Theme::tmux(out, palette::Palette::from_env());
+    render_segments(&mut out, &args.segments, &SysEnv)?;
+    render_git(
+        &mut out,
+        symbols::Set::from_env(),
+        git::parse(&std::path::PathBuf::from(args.pwd)),
+    )?;
+    render_tail(
+        &mut out,
+        args.session.as_deref(),
+        args.host,
+        args.clock,
+        &SysEnv,
+    )?;
+    out.flush()
+}
+
+pub fn render_left<Out>(out: Out, args: &Left) -> Result
+where
+    Out: std::io::Write,
+{
+    let mut out = compat::Theme::tmux(out, palette::Palette::from_env());
+    render_left_inner(&mut out, args.session.as_deref(), args.host, &SysEnv)?;
+    out.flush()
+}
+
+pub fn render_window<Out>(out: Out, args: &Window) -> Result
+where
+    Out: std::io::Write,
+{
+    let mut out = compat::Theme::tmux(out, palette::Palette::from_env());
+    let repo = git::parse(&std::path::PathBuf::from(&args.path));
+    render_window_inner(&mut out, symbols::Set::from_env(), args, &repo)?;
+    out.flush()
+}
+
+fn render_left_inner<Out, Env>(
+    out: &mut Out,
+    session: Option<&str>,
+    host: bool,
+    enver: &Env,
+) -> Result
+where
+    Out: std::io::Write,
+    Env: EnvFetcher,
+{
+    let symbols = enver.symbols();
+    let host = if host { enver.host() } else { None };
+
+    if let Some(session) = session {
+        write!(
+            out,
+            style!(fg = color!(white), bg = color!(238), " {symbol} {session} "),
+            session = escape(session),
+            symbol = symbols.get(symbol!(session))
+        )?;
+        if host.is_none() {
+            return write!(
+                out,
+                style!(fg = color!(238), bg = color!(reset), "{slant_end}"),
+                slant_end = symbols.get(symbol!(slant end))
+            );
+        }
+        write!(
+            out,
+            style!(fg = color!(238), bg = color!(239), "{slant_end}"),
+            slant_end = symbols.get(symbol!(slant end))
+        )?;
+    }
+
+    if let Some(host) = host {
+        write!(
+            out,
+            style!(fg = color!(white), bg = color!(239), " {symbol} {host} "),
+            host = escape(&host),
+            symbol = symbols.get(symbol!(host))
+        )?;
+        write!(
+            out,
+            style!(fg = color!(239), bg = color!(reset), "{slant_end}"),
+            slant_end = symbols.get(symbol!(slant end))
+        )?;
+    }
+
+    Ok(())
+}
+
+fn render_window_inner<Out>(
+    out: &mut Out,
+    symbols: symbols::Set,
+    window: &Window,
+    repo: &git::Repo,
+) -> Result
+where
+    Out: std::io::Write,
+{
+    if window.active {
+        write!(
+            out,
+            style!(fg = color!(blue), bg = color!(reset), "{slant}"),
+            slant = symbols.get(symbol!(slant))
+        )?;
+        write!(
+            out,
+            style!(fg = color!(black), bg = color!(blue), " {index} {name}"),
+            index = window.index,
+            name = escape(&window.name)
+        )?;
+    } else {
+        write!(
+            out,
+            style!(fg = color!(237), bg = color!(reset), "{slant}"),
+            slant = symbols.get(symbol!(slant))
+        )?;
+        write!(
+            out,
+            style!(fg = color!(gray), bg = color!(237), " {index} {name}"),
+            index = window.index,
+            name = escape(&window.name)
+        )?;
+    }
+
+    match repo {
+        git::Repo::None => {}
+        git::Repo::Error => write!(out, style!(fg = color!(red), " !"))?,
+        git::Repo::Regular(_, _, changes) => {
+            if changes.clean() {
+                write!(
+                    out,
+                    style!(fg = color!(green), " {branch}"),
+                    branch = symbols.get(symbol!(branch))
+                )?;
+            } else {
+                write!(
+                    out,
+                    style!(fg = color!(yellow), " {branch}"),
+                    branch = symbols.get(symbol!(branch))
+                )?;
+            }
+        }
+        git::Repo::Detached(_, _) => write!(
+            out,
+            style!(fg = color!(magenta), " {reference}"),
+            reference = symbols.get(symbol!(ref))
+        )?,
+        git::Repo::Pending(_, pending, _) => write!(
+            out,
+            style!(fg = color!(cyan), " {pending}"),
+            pending = symbols.get(pending_symbol(*pending))
+        )?,
+        git::Repo::New(_) => write!(
+            out,
+            style!(fg = color!(cyan), " {new}"),
+            new = symbols.get(symbol!(new))
+        )?,
+    }
+
+    if window.active {
+        write!(
+            out,
+            style!(fg = color!(blue), bg = color!(reset), " {slant_end}"),
+            slant_end = symbols.get(symbol!(slant end))
+        )
+    } else {
+        write!(
+            out,
+            style!(fg = color!(237), bg = color!(reset), " {slant_end}"),
+            slant_end = symbols.get(symbol!(slant end))
+        )
+    }
+}
+
+fn render_segments<Out, Env>(out: &mut Out, segments: &[Segment], enver: &Env) -> Result
+where
+    Out: std::io::Write,
+    Env: EnvFetcher,
+{
+    let symbols = enver.symbols();
+    let mut on_bg = false;
+    let mut separate = |out: &mut Out| {
+        if on_bg {
+            write!(
+                out,
+                style!(fg = color!(gray), "{slant_thin}"),
+                slant_thin = symbols.get(symbol!(slant thin))
+            )
+        } else {
+            on_bg = true;
+            write!(
+                out,
+                style!(fg = color!(dark gray), "{slant}"),
+                slant = symbols.get(symbol!(slant))
+            )?;
+            write!(out, style!(bg = color!(dark gray)))
+        }
+    };
+
+    for segment in segments {
+        match segment {
+            Segment::Load => {
+                if let Some(load) = enver.load() {
+                    separate(out)?;
+                    let per_cpu = load.per_cpu();
+                    if per_cpu >= 1.0 {
+                        write!(out, style!(fg = color!(red)))?;
+                    } else if per_cpu >= 0.7 {
+                        write!(out, style!(fg = color!(yellow)))?;
+                    } else {
+                        write!(out, style!(fg = color!(gray)))?;
+                    }
+                    write!(
+                        out,
+                        " {symbol} {load:.2} ",
+                        load = load.average,
+                        symbol = symbols.get(symbol!(load))
+                    )?;
+                }
+            }
+            Segment::Memory => {
+                if let Some(memory) = enver.memory() {
+                    separate(out)?;
+                    let used = memory.used_percent();
+                    if used >= 90 {
+                        write!(out, style!(fg = color!(red)))?;
+                    } else if used >= 75 {
+                        write!(out, style!(fg = color!(yellow)))?;
+                    } else {
+                        write!(out, style!(fg = color!(gray)))?;
+                    }
+                    write!(
+                        out,
+                        " {memory} {used}% ",
+                        used = used,
+                        memory = symbols.get(symbol!(memory))
+                    )?;
+                }
+            }
+        }
+    }
+
+    Ok(())
+}
+
+fn render_tail<Out, Env>(
+    out: &mut Out,
+    session: Option<&str>,
+    host: bool,
+    clock: bool,
+    enver: &Env,
+) -> Result
+where
+    Out: std::io::Write,
+    Env: EnvFetcher,
+{
+    let symbols = enver.symbols();
+
+    if let Some(session) = session {
+        write!(
+            out,
+            style!(fg = color!(238), "{slant}"),
+            slant = symbols.get(symbol!(slant))
+        )?;
+        write!(
+            out,
+            style!(fg = color!(white), bg = color!(238), " {symbol} {session} "),
+            session = escape(session),
+            symbol = symbols.get(symbol!(session))
+        )?;
+    }
+
+    if host && let Some(host) = enver.host() {
+        write!(
+            out,
+            style!(fg = color!(239), "{slant}"),
+            slant = symbols.get(symbol!(slant))
+        )?;
+        write!(
+            out,
+            style!(fg = color!(white), bg = color!(239), " {symbol} {host} "),
+            host = escape(&host),
+            symbol = symbols.get(symbol!(host))
+        )?;
+    }
+
+    if clock {
+        let (hour, minute) = enver.clock();
+        write!(
+            out,
+            style!(fg = color!(blue), "{slant}"),
+            slant = symbols.get(symbol!(slant))
+        )?;
+        write!(
+            out,
+            style!(
+                fg = color!(black),
+                bg = color!(blue),
+                " {hour:02}:{minute:02} "
+            ),
+            hour = hour,
+            minute = minute
+        )?;
+    }
+
+    Ok(())
 }
 
-fn render_git<Out>(mut out: Out, repo: git::Repo) -> Result
+fn render_git<Out>(mut out: Out, symbols: symbols::Set, repo: git::Repo) -> Result
 where
     Out: std::io::Write,
 {
     match repo {
         git::Repo::None | git::Repo::Error => Ok(()),
         git::Repo::Regular(head, sync, changes) => {
-            write!(out, style!(fg = color!(237), symbol!(slant)))?;
             write!(
                 out,
-                style!(fg = color!(magenta), bg = color!(237), " ", symbol!(branch))
+                style!(fg = color!(237), "{slant}"),
+                slant = symbols.get(symbol!(slant))
+            )?;
+            write!(
+                out,
+                style!(fg = color!(magenta), bg = color!(237), " {branch}"),
+                branch = symbols.get(symbol!(branch))
             )?;
             write!(out, style!(fg = color!(gray), "{head} "), head = head)?;
-            let changed_bg = render_changes(&mut out, changes)?;
-            render_sync(&mut out, sync, changed_bg)?;
+            let changed_bg = render_changes(&mut out, symbols, changes)?;
+            render_sync(&mut out, symbols, sync, changed_bg)?;
             out.flush()
         }
         git::Repo::Detached(head, changes) => {
-            write!(out, style!(fg = color!(237), symbol!(slant)))?;
             write!(
                 out,
-                style!(fg = color!(magenta), bg = color!(237), " ", symbol!(branch))
+                style!(fg = color!(237), "{slant}"),
+                slant = symbols.get(symbol!(slant))
+            )?;
+            write!(
+                out,
+                style!(fg = color!(magenta), bg = color!(237), " {branch}"),
+                branch = symbols.get(symbol!(branch))
             )?;
             write!(out, style!(fg = color!(gray), "{head} "), head = head)?;
-            render_changes(&mut out, changes)?;
+            render_changes(&mut out, symbols, changes)?;
             out.flush()
         }
         git::Repo::Pending(head, pending, changes) => {
-            write!(out, style!(fg = color!(237), symbol!(slant)))?;
             write!(
                 out,
-                style!(fg = color!(magenta), bg = color!(237), " ", symbol!(branch))
+                style!(fg = color!(237), "{slant}"),
+                slant = symbols.get(symbol!(slant))
+            )?;
+            write!(
+                out,
+                style!(fg = color!(magenta), bg = color!(237), " {branch}"),
+                branch = symbols.get(symbol!(branch))
             )?;
             write!(
                 out,
                 style!(fg = color!(gray), "{head} {pending}"),
                 head = head,
-                pending = pending_symbol(pending),
+                pending = symbols.get(pending_symbol(pending)),
             )?;
-            render_changes(&mut out, changes)?;
+            render_changes(&mut out, symbols, changes)?;
             out.flush()
         }
         git::Repo::New(changes) => {
-            render_changes(&mut out, changes)?;
+            render_changes(&mut out, symbols, changes)?;
             out.flush()
         }
     }
 }
 
-fn render_changes<Out>(out: &mut Out, changes: git::Changes) -> Result<bool>
+fn render_changes<Out>(out: &mut Out, symbols: symbols::Set, changes: git::Changes) -> Result<bool>
 where
     Out: std::io::Write,
 {
     let mut changed_bg = false;
     if changes.added > 0 {
-        write!(out, style!(fg = color!(dark gray), symbol!(slant)))?;
+        write!(
+            out,
+            style!(fg = color!(dark gray), "{slant}"),
+            slant = symbols.get(symbol!(slant))
+        )?;
         write!(
             out,
             style!(fg = color!(green), bg = color!(dark gray), " +{added}"),
@@ -145,7 +494,11 @@ where
                 removed = changes.removed
             )?;
         } else {
-            write!(out, style!(fg = color!(dark gray), symbol!(slant)))?;
+            write!(
+                out,
+                style!(fg = color!(dark gray), "{slant}"),
+                slant = symbols.get(symbol!(slant))
+            )?;
             write!(
                 out,
                 style!(fg = color!(red), bg = color!(dark gray), " -{removed}"),
@@ -163,7 +516,11 @@ where
                 modified = changes.modified
             )?;
         } else {
-            write!(out, style!(fg = color!(dark gray), symbol!(slant)))?;
+            write!(
+                out,
+                style!(fg = color!(dark gray), "{slant}"),
+                slant = symbols.get(symbol!(slant))
+            )?;
             write!(
                 out,
                 style!(fg = color!(blue), bg = color!(dark gray), " ~{modified}"),
@@ -181,7 +538,11 @@ where
                 conflicted = changes.conflicted
             )?;
         } else {
-            write!(out, style!(fg = color!(dark gray), symbol!(slant)))?;
+            write!(
+                out,
+                style!(fg = color!(dark gray), "{slant}"),
+                slant = symbols.get(symbol!(slant))
+            )?;
             write!(
                 out,
                 style!(
@@ -202,54 +563,74 @@ where
     Ok(changed_bg)
 }
 
-fn render_sync<Out>(out: &mut Out, sync: git::Sync, changed_bg: bool) -> Result
+fn render_sync<Out>(
+    out: &mut Out,
+    symbols: symbols::Set,
+    sync: git::Sync,
+    changed_bg: bool,
+) -> Result
 where
     Out: std::io::Write,
 {
-    fn add_slant<Out>(out: &mut Out, changed_bg: bool) -> Result
+    fn add_slant<Out>(out: &mut Out, symbols: symbols::Set, changed_bg: bool) -> Result
     where
         Out: std::io::Write,
     {
         if changed_bg {
             write!(
                 out,
-                style!(fg = color!(gray), " ", symbol!(slant thin), " ")
+                style!(fg = color!(gray), " {slant_thin} "),
+                slant_thin = symbols.get(symbol!(slant thin))
             )
         } else {
-            write!(out, style!(fg = color!(gray), symbol!(slant thin)))?;
+            write!(
+                out,
+                style!(fg = color!(gray), "{slant_thin}"),
+                slant_thin = symbols.get(symbol!(slant thin))
+            )?;
             write!(out, style!(bg = color!(dark gray), " "))
         }
     }
 
     match sync {
         git::Sync::Local => {
-            add_slant(out, changed_bg)?;
-            write!(out, style!(fg = color!(cyan), symbol!(local), " local "))
+            add_slant(out, symbols, changed_bg)?;
+            write!(
+                out,
+                style!(fg = color!(cyan), "{local} local "),
+                local = symbols.get(symbol!(local))
+            )
         }
         git::Sync::Gone => {
-            add_slant(out, changed_bg)?;
-            write!(out, style!(fg = color!(magenta), symbol!(gone), " gone "))
+            add_slant(out, symbols, changed_bg)?;
+            write!(
+                out,
+                style!(fg = color!(magenta), "{gone} gone "),
+                gone = symbols.get(symbol!(gone))
+            )
         }
         git::Sync::Tracked { ahead, behind } => {
             let has_ahead = ahead > 0;
             if has_ahead {
-                add_slant(out, changed_bg)?;
+                add_slant(out, symbols, changed_bg)?;
                 write!(
                     out,
-                    style!(fg = color!(yellow), symbol!(ahead), "{ahead} "),
-                    ahead = ahead
+                    style!(fg = color!(yellow), "{symbol}{ahead} "),
+                    ahead = ahead,
+                    symbol = symbols.get(symbol!(ahead))
                 )?;
             }
 
             if behind > 0 {
                 if !has_ahead {
-                    add_slant(out, changed_bg)?;
+                    add_slant(out, symbols, changed_bg)?;
                 }
 
                 write!(
                     out,
-                    style!(fg = color!(red), symbol!(behind), "{behind} "),
-                    behind = behind
+                    style!(fg = color!(red), "{symbol}{behind} "),
+                    behind = behind,
+                    symbol = symbols.get(symbol!(behind))
                 )?;
             }
 
@@ -268,3 +649,394 @@ const fn pending_symbol(pending: git::Pending) -> &'static str {
         git::Pending::Mailbox => symbol!(mailbox),
     }
 }
+
+// tmux reads a `#` as the start of a format or a style, so text from the user is doubled up
+fn escape(text: &str) -> String {
+    text.replace('#', "##")
+}
+
+trait EnvFetcher {
+    fn load(&self) -> Option<system::Load>;
+    fn memory(&self) -> Option<system::Memory>;
+    fn host(&self) -> Option<String>;
+    fn clock(&self) -> (u32, u32);
+    fn symbols(&self) -> symbols::Set;
+}
+
+#[derive(Copy, Clone)]
+struct SysEnv;
+
+impl EnvFetcher for SysEnv {
+    fn load(&self) -> Option<system::Load> {
+        system::load()
+    }
+
+    fn memory(&self) -> Option<system::Memory> {
+        system::memory()
+    }
+
+    fn host(&self) -> Option<String> {
+        user::hostname()
+    }
+
+    fn clock(&self) -> (u32, u32) {
+        let time = chrono::DateTime::<chrono::Local>::from(std::time::SystemTime::now());
+        (time.hour(), time.minute())
+    }
+
+    fn symbols(&self) -> symbols::Set {
+        symbols::Set::from_env()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test;
+
+    #[derive(Default)]
+    struct MockEnv {
+        load: Option<system::Load>,
+        memory: Option<system::Memory>,
+        host: Option<String>,
+        symbols: symbols::Set,
+    }
+
+    impl EnvFetcher for MockEnv {
+        fn load(&self) -> Option<system::Load> {
+            self.load
+        }
+
+        fn memory(&self) -> Option<system::Memory> {
+            self.memory
+        }
+
+        fn host(&self) -> Option<String> {
+            self.host.clone()
+        }
+
+        fn clock(&self) -> (u32, u32) {
+            (9, 5)
+        }
+
+        fn symbols(&self) -> symbols::Set {
+            self.symbols
+        }
+    }
+
+    #[test]
+    fn no_segments() {
+        let result = test(|s| {
+            render_segments(
+                s,
+                &[],
+                &MockEnv {
+                    load: Some(system::Load {
+                        average: 0.5,
+                        cpus: 4,
+                    }),
+                    ..MockEnv::default()
+                },
+            )
+        });
+        assert_eq!(result, "");
+    }
+
+    #[test]
+    fn load_and_memory() {
+        let result = test(|s| {
+            render_segments(
+                s,
+                &[Segment::Load, Segment::Memory],
+                &MockEnv {
+                    load: Some(system::Load {
+                        average: 0.5,
+                        cpus: 4,
+                    }),
+                    memory: Some(system::Memory {
+                        total: 1000,
+                        available: 200,
+                    }),
+                    ..MockEnv::default()
+                },
+            )
+        });
+        let expected = concat!(
+            style!(fg = color!(dark gray), symbol!(slant)),
+            style!(bg = color!(dark gray)),
+            style!(fg = color!(gray)),
+            " ",
+            symbol!(load),
+            " 0.50 ",
+            style!(fg = color!(gray), symbol!(slant thin)),
+            style!(fg = color!(yellow)),
+            " ",
+            symbol!(memory),
+            " 80% ",
+        );
+        assert_eq!(result, expected);
+    }
+
+    #[test]
+    fn high_load() {
+        let result = test(|s| {
+            render_segments(
+                s,
+                &[Segment::Memory, Segment::Load],
+                &MockEnv {
+                    load: Some(system::Load {
+                        average: 4.25,
+                        cpus: 4,
+                    }),
+                    ..MockEnv::default()
+                },
+            )
+        });
+        let expected = concat!(
+            style!(fg = color!(dark gray), symbol!(slant)),
+            style!(bg = color!(dark gray)),
+            style!(fg = color!(red)),
+            " ",
+            symbol!(load),
+            " 4.25 ",
+        );
+        assert_eq!(result, expected);
+    }
+
+    #[test]
+    fn tail() {
+        let result = test(|s| {
+            render_tail(
+                s,
+                Some("main"),
+                true,
+                true,
+                &MockEnv {
+                    host: Some(String::from("box")),
+                    ..MockEnv::default()
+                },
+            )
+        });
+        let expected = concat!(
+            style!(fg = color!(238), symbol!(slant)),
+            style!(
+                fg = color!(white),
+                bg = color!(238),
+                " ",
+                symbol!(session),
+                " main "
+            ),
+            style!(fg = color!(239), symbol!(slant)),
+            style!(
+                fg = color!(white),
+                bg = color!(239),
+                " ",
+                symbol!(host),
+                " box "
+            ),
+            style!(fg = color!(blue), symbol!(slant)),
+            style!(fg = color!(black), bg = color!(blue), " 09:05 "),
+        );
+        assert_eq!(result, expected);
+    }
+
+    #[test]
+    fn tail_without_host() {
+        let result = test(|s| render_tail(s, None, true, false, &MockEnv::default()));
+        assert_eq!(result, "");
+    }
+
+    #[test]
+    fn left() {
+        let result = test(|s| {
+            render_left_inner(
+                s,
+                Some("main"),
+                true,
+                &MockEnv {
+                    host: Some(String::from("box")),
+                    ..MockEnv::default()
+                },
+            )
+        });
+        let expected = concat!(
+            style!(
+                fg = color!(white),
+                bg = color!(238),
+                " ",
+                symbol!(session),
+                " main "
+            ),
+            style!(fg = color!(238), bg = color!(239), symbol!(slant end)),
+            style!(
+                fg = color!(white),
+                bg = color!(239),
+                " ",
+                symbol!(host),
+                " box "
+            ),
+            style!(fg = color!(239), bg = color!(reset), symbol!(slant end)),
+        );
+        assert_eq!(result, expected);
+    }
+
+    #[test]
+    fn left_escaped() {
+        let result = test(|s| {
+            render_left_inner(
+                s,
+                Some("a#[fg=red]"),
+                true,
+                &MockEnv {
+                    host: Some(String::from("#h")),
+                    ..MockEnv::default()
+                },
+            )
+        });
+        let expected = concat!(
+            style!(
+                fg = color!(white),
+                bg = color!(238),
+                " ",
+                symbol!(session),
+                " a##[fg=red] "
+            ),
+            style!(fg = color!(238), bg = color!(239), symbol!(slant end)),
+            style!(
+                fg = color!(white),
+                bg = color!(239),
+                " ",
+                symbol!(host),
+                " ##h "
+            ),
+            style!(fg = color!(239), bg = color!(reset), symbol!(slant end)),
+        );
+        assert_eq!(result, expected);
+    }
+
+    #[test]
+    fn left_session_only() {
+        let result = test(|s| render_left_inner(s, Some("main"), true, &MockEnv::default()));
+        let expected = concat!(
+            style!(
+                fg = color!(white),
+                bg = color!(238),
+                " ",
+                symbol!(session),
+                " main "
+            ),
+            style!(fg = color!(238), bg = color!(reset), symbol!(slant end)),
+        );
+        assert_eq!(result, expected);
+    }
+
+    #[test]
+    fn window_active() {
+        let window = Window {
+            index: String::from("1"),
+            name: String::from("zsh"),
+            active: true,
+            path: String::new(),
+        };
+        let result = test(|s| {
+            render_window_inner(
+                s,
+                symbols::Set::Nerd,
+                &window,
+                &git::Repo::Regular(
+                    String::from("main"),
+                    git::Sync::Local,
+                    git::Changes {
+                        added: 0,
+                        modified: 1,
+                        removed: 0,
+                        conflicted: 0,
+                    },
+                ),
+            )
+        });
+        let expected = concat!(
+            style!(fg = color!(blue), bg = color!(reset), symbol!(slant)),
+            style!(fg = color!(black), bg = color!(blue), " 1 zsh"),
+            style!(fg = color!(yellow), " ", symbol!(branch)),
+            style!(
+                fg = color!(blue),
+                bg = color!(reset),
+                " ",
+                symbol!(slant end)
+            ),
+        );
+        assert_eq!(result, expected);
+    }
+
+    #[test]
+    fn window_inactive() {
+        let window = Window {
+            index: String::from("2"),
+            name: String::from("vim"),
+            active: false,
+            path: String::new(),
+        };
+        let result =
+            test(|s| render_window_inner(s, symbols::Set::Nerd, &window, &git::Repo::None));
+        let expected = concat!(
+            style!(fg = color!(237), bg = color!(reset), symbol!(slant)),
+            style!(fg = color!(gray), bg = color!(237), " 2 vim"),
+            style!(
+                fg = color!(237),
+                bg = color!(reset),
+                " ",
+                symbol!(slant end)
+            ),
+        );
+        assert_eq!(result, expected);
+    }
+
+    #[test]
+    fn window_escaped() {
+        let window = Window {
+            index: String::from("1"),
+            name: String::from("a#[fg=red]b"),
+            active: false,
+            path: String::new(),
+        };
+        let result =
+            test(|s| render_window_inner(s, symbols::Set::Nerd, &window, &git::Repo::None));
+        let expected = concat!(
+            style!(fg = color!(237), bg = color!(reset), symbol!(slant)),
+            style!(fg = color!(gray), bg = color!(237), " 1 a##[fg=red]b"),
+            style!(
+                fg = color!(237),
+                bg = color!(reset),
+                " ",
+                symbol!(slant end)
+            ),
+        );
+        assert_eq!(result, expected);
+    }
+
+    #[test]
+    fn window_unicode() {
+        let window = Window {
+            index: String::from("3"),
+            name: String::from("git"),
+            active: false,
+            path: String::new(),
+        };
+        let result = test(|s| {
+            render_window_inner(
+                s,
+                symbols::Set::Unicode,
+                &window,
+                &git::Repo::Detached(String::from("abc123"), git::Changes::default()),
+            )
+        });
+        let expected = concat!(
+            style!(fg = color!(237), bg = color!(reset), "◥"),
+            style!(fg = color!(gray), bg = color!(237), " 3 git"),
+            style!(fg = color!(magenta), " ➦"),
+            style!(fg = color!(237), bg = color!(reset), " ◣"),
+        );
+        assert_eq!(result, expected);
+    }
+}
@@ -0,0 +1,107 @@
+use super::scanner::Scanner;
+use crate::Result;
+use crate::palette::Depth;
+
+pub struct Downgrade<Out>
+where
+    Out: std::io::Write,
+{
+    scanner: Scanner<Out>,
+    depth: Depth,
+}
+
+impl<Out> Downgrade<Out>
+where
+    Out: std::io::Write,
+{
+    pub fn new(out: Out, depth: Depth) -> Self {
+        Self {
+            scanner: Scanner::ansi(out),
+            depth,
+        }
+    }
+
+    pub fn from_env(out: Out) -> Self {
+        Self::new(out, Depth::from_env())
+    }
+}
+
+impl<Out> std::io::Write for Downgrade<Out>
+where
+    Out: std::io::Write,
+{
+    fn write(&mut self, buf: &[u8]) -> Result<usize> {
+        if self.depth == Depth::True {
+            return self.scanner.passthrough(buf);
+        }
+
+        let depth = self.depth;
+        self.scanner.write(buf, |_, color| color.downgrade(depth))
+    }
+
+    fn flush(&mut self) -> Result {
+        self.scanner.flush()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Write;
+
+    fn downgrade(input: &[&str], depth: Depth) -> String {
+        let mut output = Vec::new();
+        {
+            let mut downgrade = Downgrade::new(&mut output, depth);
+            for input in input {
+                downgrade.write_all(input.as_bytes()).unwrap();
+            }
+            downgrade.flush().unwrap();
+        }
+        String::from_utf8(output).unwrap()
+    }
+
+    #[test]
+    fn truecolor() {
+        let input = concat!(
+            style!(fg = color!([38, 139, 210]), bg = color!([7, 54, 66])),
+            "abc"
+        );
+        assert_eq!(downgrade(&[input], Depth::True), input);
+    }
+
+    #[test]
+    fn indexed() {
+        let input = concat!(
+            style!(fg = color!([38, 139, 210]), bg = color!([7, 54, 66])),
+            "abc",
+            style!(fg = color!([23]), bg = color!(blue)),
+            style!(reset)
+        );
+        let expected = concat!(
+            style!(fg = color!([32]), bg = color!([235])),
+            "abc",
+            style!(fg = color!([23]), bg = color!(blue)),
+            style!(reset)
+        );
+        assert_eq!(downgrade(&[input], Depth::Indexed), expected);
+    }
+
+    #[test]
+    fn base() {
+        let input = concat!(
+            style!(fg = color!([38, 139, 210]), bg = color!([236])),
+            "abc",
+            style!(reset to fg = color!([30])),
+        );
+        let expected = concat!(
+            style!(fg = color!(blue), bg = color!(black)),
+            "abc",
+            style!(reset to fg = color!(cyan)),
+        );
+        assert_eq!(
+            downgrade(&[&input[..6], &input[6..]], Depth::Base),
+            expected
+        );
+    }
+}
@@ -1,5 +1,12 @@
+mod downgrade;
+mod plain;
+mod scanner;
+mod theme;
 mod win;
 mod zsh;
 
+pub use downgrade::Downgrade;
+pub use plain::Plain;
+pub use theme::Theme;
 pub use win::Win;
 pub use zsh::Zsh;
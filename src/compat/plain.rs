@@ -0,0 +1,142 @@
+use crate::Result;
+
+#[derive(Eq, PartialEq)]
+enum State {
+    Normal,
+    Escape,
+    Sequence,
+}
+
+pub struct Plain<Out>
+where
+    Out: std::io::Write,
+{
+    out: Out,
+    color: bool,
+    state: State,
+}
+
+impl<Out> Plain<Out>
+where
+    Out: std::io::Write,
+{
+    pub fn new(out: Out, color: bool) -> Self {
+        Self {
+            out,
+            color,
+            state: State::Normal,
+        }
+    }
+
+    pub fn from_env(out: Out) -> Self {
+        let dumb = std::env::var("TERM").is_ok_and(|term| term == "dumb");
+        let no_color = std::env::var_os("NO_COLOR").is_some_and(|value| !value.is_empty());
+        Self::new(out, !dumb && !no_color)
+    }
+
+    fn process(&mut self, buf: &[u8]) -> Result {
+        let mut cursor = 0;
+        let mut i = 0;
+
+        while i < buf.len() {
+            let byte = buf[i];
+            match self.state {
+                State::Normal => {
+                    if byte == 0x1b {
+                        self.out.write_all(&buf[cursor..i])?;
+                        self.state = State::Escape;
+                        i += 1;
+                        cursor = i;
+                        continue;
+                    }
+                }
+                State::Escape => {
+                    if byte == b'[' {
+                        self.state = State::Sequence;
+                        i += 1;
+                        cursor = i;
+                    } else {
+                        self.state = State::Normal;
+                    }
+                    continue;
+                }
+                State::Sequence => {
+                    if (0x40..=0x7e).contains(&byte) {
+                        self.state = State::Normal;
+                    }
+                    i += 1;
+                    cursor = i;
+                    continue;
+                }
+            }
+            i += 1;
+        }
+
+        if cursor < buf.len() {
+            self.out.write_all(&buf[cursor..])?;
+        }
+
+        Ok(())
+    }
+}
+
+impl<Out> std::io::Write for Plain<Out>
+where
+    Out: std::io::Write,
+{
+    fn write(&mut self, buf: &[u8]) -> Result<usize> {
+        if self.color {
+            return self.out.write_all(buf).map(|()| buf.len());
+        }
+
+        self.process(buf)?;
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> Result {
+        self.out.flush()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Write;
+
+    fn plain(input: &[&str], color: bool) -> String {
+        let mut output = Vec::new();
+        {
+            let mut plain = Plain::new(&mut output, color);
+            for input in input {
+                plain.write_all(input.as_bytes()).unwrap();
+            }
+            plain.flush().unwrap();
+        }
+        String::from_utf8(output).unwrap()
+    }
+
+    #[test]
+    fn passthrough() {
+        let input = concat!(style!(fg = color!(red)), symbol!(error), " abc");
+        assert_eq!(plain(&[input], true), input);
+    }
+
+    #[test]
+    fn strip_color() {
+        let input = concat!(
+            style!(fg = color!(black), bg = color!(blue)),
+            " ~/path ",
+            style!(fg = color!([23]), "12:00"),
+            style!(reset)
+        );
+        assert_eq!(plain(&[input], false), " ~/path 12:00");
+    }
+
+    #[test]
+    fn split_escape() {
+        assert_eq!(
+            plain(&["abc\x1b", "[38;5", ";2mdef\x1b[m"], false),
+            "abcdef"
+        );
+    }
+}
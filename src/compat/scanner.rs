@@ -0,0 +1,259 @@
+use crate::Result;
+use crate::palette::{Color, Layer};
+
+#[derive(Copy, Clone, Eq, PartialEq)]
+enum Syntax {
+    Ansi,
+    Tmux,
+}
+
+impl Syntax {
+    fn open(self) -> u8 {
+        match self {
+            Self::Ansi => 0x1b,
+            Self::Tmux => b'#',
+        }
+    }
+
+    fn close(self, byte: u8) -> bool {
+        match self {
+            Self::Ansi => (0x40..=0x7e).contains(&byte),
+            Self::Tmux => byte == b']',
+        }
+    }
+}
+
+// Finds the style sequences in the output, even when split across writes, and maps their colors
+pub struct Scanner<Out>
+where
+    Out: std::io::Write,
+{
+    out: Out,
+    syntax: Syntax,
+    sequence: Vec<u8>,
+    background: Color,
+}
+
+impl<Out> Scanner<Out>
+where
+    Out: std::io::Write,
+{
+    pub fn ansi(out: Out) -> Self {
+        Self {
+            out,
+            syntax: Syntax::Ansi,
+            sequence: Vec::new(),
+            background: Color::Reset,
+        }
+    }
+
+    pub fn tmux(out: Out) -> Self {
+        Self {
+            out,
+            syntax: Syntax::Tmux,
+            sequence: Vec::new(),
+            background: Color::Reset,
+        }
+    }
+
+    pub fn passthrough(&mut self, buf: &[u8]) -> Result<usize> {
+        self.out.write_all(buf).map(|()| buf.len())
+    }
+
+    pub fn write<F>(&mut self, buf: &[u8], map: F) -> Result<usize>
+    where
+        F: Fn(Layer, Color) -> Color,
+    {
+        let mut cursor = 0;
+
+        for (i, byte) in buf.iter().copied().enumerate() {
+            if self.sequence.is_empty() {
+                if byte == self.syntax.open() {
+                    self.out.write_all(&buf[cursor..i])?;
+                    self.sequence.push(byte);
+                    cursor = i + 1;
+                }
+                continue;
+            }
+
+            self.sequence.push(byte);
+            cursor = i + 1;
+
+            if self.sequence.len() == 2 {
+                if byte != b'[' {
+                    let sequence = std::mem::take(&mut self.sequence);
+                    self.out.write_all(&sequence)?;
+                }
+            } else if self.syntax.close(byte) {
+                let sequence = std::mem::take(&mut self.sequence);
+                self.rewrite(&sequence, &map)?;
+            }
+        }
+
+        if cursor < buf.len() {
+            self.out.write_all(&buf[cursor..])?;
+        }
+
+        Ok(buf.len())
+    }
+
+    pub fn flush(&mut self) -> Result {
+        if !self.sequence.is_empty() {
+            let sequence = std::mem::take(&mut self.sequence);
+            self.out.write_all(&sequence)?;
+        }
+        self.out.flush()
+    }
+
+    fn rewrite<F>(&mut self, sequence: &[u8], map: F) -> Result
+    where
+        F: Fn(Layer, Color) -> Color,
+    {
+        let body = sequence
+            .get(2..sequence.len() - 1)
+            .and_then(|body| std::str::from_utf8(body).ok());
+
+        match (self.syntax, body) {
+            (Syntax::Ansi, Some(body)) if sequence.ends_with(b"m") => {
+                let body = recolor_ansi(body, &mut self.background, map);
+                write!(self.out, "\x1b[{body}m")
+            }
+            (Syntax::Tmux, Some(body)) => {
+                let body = recolor_tmux(body, &mut self.background, map);
+                write!(self.out, "#[{body}]")
+            }
+            _ => self.out.write_all(sequence),
+        }
+    }
+}
+
+// A foreground matching the background being drawn on is a separator closing that background,
+// so it is mapped as one
+fn layer(background: &mut Color, is_background: bool, color: Color) -> Layer {
+    if is_background {
+        *background = color;
+        Layer::Background
+    } else if color == *background {
+        Layer::Background
+    } else {
+        Layer::Foreground
+    }
+}
+
+// Maps every color in the parameters of an SGR sequence
+fn recolor_ansi<F>(body: &str, background: &mut Color, map: F) -> String
+where
+    F: Fn(Layer, Color) -> Color,
+{
+    let params = body.split(';').collect::<Vec<_>>();
+    let mut rewritten = Vec::with_capacity(params.len());
+
+    let mut i = 0;
+    while i < params.len() {
+        if let Some((prefix, color, length)) = parse_ansi(&params[i..]) {
+            let layer = layer(background, prefix == '4', color);
+            rewritten.push(format!("{prefix}{color}", color = map(layer, color).sgr()));
+            i += length;
+        } else {
+            if matches!(params[i], "" | "0") {
+                *background = Color::Reset;
+            }
+            rewritten.push(String::from(params[i]));
+            i += 1;
+        }
+    }
+
+    rewritten.join(";")
+}
+
+// Returns the layer (`3` or `4`), the color and how many parameters were consumed
+fn parse_ansi(params: &[&str]) -> Option<(char, Color, usize)> {
+    let mut first = params.first()?.chars();
+    let layer = first
+        .next()
+        .filter(|layer| *layer == '3' || *layer == '4')?;
+    let kind = first.next()?;
+    if first.next().is_some() {
+        return None;
+    }
+
+    match (kind, params.get(1..)) {
+        ('0'..='7', _) => {
+            let base = u8::try_from(kind.to_digit(8)?).ok()?;
+            Some((layer, Color::Base(base), 1))
+        }
+        ('9', _) => Some((layer, Color::Reset, 1)),
+        ('8', Some(["5", index, ..])) => Some((layer, Color::Indexed(index.parse().ok()?), 3)),
+        ('8', Some(["2", r, g, b, ..])) => Some((
+            layer,
+            Color::Rgb(r.parse().ok()?, g.parse().ok()?, b.parse().ok()?),
+            5,
+        )),
+        _ => None,
+    }
+}
+
+// Maps the `fg` and `bg` colors of a tmux style, leaving every other attribute as is
+fn recolor_tmux<F>(body: &str, background: &mut Color, map: F) -> String
+where
+    F: Fn(Layer, Color) -> Color,
+{
+    body.split(',')
+        .map(|attribute| {
+            attribute
+                .split_once('=')
+                .filter(|(key, _)| *key == "fg" || *key == "bg")
+                .and_then(|(key, value)| Some((key, Color::parse_tmux(value)?)))
+                .map_or_else(
+                    || String::from(attribute),
+                    |(key, color)| {
+                        let layer = layer(background, key == "bg", color);
+                        format!("{key}={}", map(layer, color).tmux())
+                    },
+                )
+        })
+        .collect::<Vec<_>>()
+        .join(",")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ansi_params() {
+        let swap = |_, color| match color {
+            Color::Base(4) => Color::Indexed(74),
+            color => color,
+        };
+        let mut background = Color::Reset;
+        assert_eq!(
+            recolor_ansi("1;34;48;5;4", &mut background, swap),
+            "1;38;5;74;48;5;4"
+        );
+        assert_eq!(recolor_ansi("38;5", &mut background, swap), "38;5");
+    }
+
+    #[test]
+    fn tmux_attributes() {
+        let swap = |_, _| Color::Reset;
+        assert_eq!(
+            recolor_tmux("fg=blue,bold,bg=colour23,us=red", &mut Color::Reset, swap),
+            "fg=default,bold,bg=default,us=red"
+        );
+    }
+
+    #[test]
+    fn separator_layer() {
+        let mark = |layer, color| match (layer, color) {
+            (Layer::Background, Color::Base(0)) => Color::Indexed(254),
+            (_, color) => color,
+        };
+        let mut background = Color::Reset;
+        assert_eq!(recolor_ansi(";40", &mut background, mark), ";48;5;254");
+        assert_eq!(recolor_ansi("30;44", &mut background, mark), "38;5;254;44");
+        assert_eq!(recolor_ansi("30", &mut background, mark), "30");
+        assert_eq!(recolor_ansi("0", &mut background, mark), "0");
+        assert_eq!(background, Color::Reset);
+    }
+}
@@ -0,0 +1,138 @@
+use super::scanner::Scanner;
+use crate::Result;
+use crate::palette::Palette;
+
+pub struct Theme<Out>
+where
+    Out: std::io::Write,
+{
+    scanner: Scanner<Out>,
+    palette: Palette,
+}
+
+impl<Out> Theme<Out>
+where
+    Out: std::io::Write,
+{
+    pub fn new(out: Out, palette: Palette) -> Self {
+        Self {
+            scanner: Scanner::ansi(out),
+            palette,
+        }
+    }
+
+    pub fn tmux(out: Out, palette: Palette) -> Self {
+        Self {
+            scanner: Scanner::tmux(out),
+            palette,
+        }
+    }
+
+    pub fn from_env(out: Out) -> Self {
+        Self::new(out, Palette::from_env())
+    }
+}
+
+impl<Out> std::io::Write for Theme<Out>
+where
+    Out: std::io::Write,
+{
+    fn write(&mut self, buf: &[u8]) -> Result<usize> {
+        if self.palette == Palette::Dark {
+            return self.scanner.passthrough(buf);
+        }
+
+        let palette = self.palette;
+        self.scanner
+            .write(buf, |layer, color| palette.get(layer, color))
+    }
+
+    fn flush(&mut self) -> Result {
+        self.scanner.flush()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Write;
+
+    fn theme(input: &[&str], palette: Palette, tmux: bool) -> String {
+        let mut output = Vec::new();
+        {
+            let mut theme = if tmux {
+                Theme::tmux(&mut output, palette)
+            } else {
+                Theme::new(&mut output, palette)
+            };
+            for input in input {
+                theme.write_all(input.as_bytes()).unwrap();
+            }
+            theme.flush().unwrap();
+        }
+        String::from_utf8(output).unwrap()
+    }
+
+    #[test]
+    fn dark() {
+        let input = concat!(style!(fg = color!(black), bg = color!(blue)), " ~ ");
+        assert_eq!(theme(&[input], Palette::Dark, false), input);
+    }
+
+    #[test]
+    fn light() {
+        let input = concat!(
+            style!(fg = color!(black), bg = color!(blue)),
+            " ~ ",
+            style!(reset to fg = color!(blue), symbol!(div)),
+            style!(fg = color!([23]), "12:00", style!(reset))
+        );
+        let expected = concat!(
+            style!(fg = color!(black), bg = color!([74])),
+            " ~ ",
+            style!(reset to fg = color!([74]), symbol!(div)),
+            style!(fg = color!([31]), "12:00", style!(reset))
+        );
+        assert_eq!(theme(&[input], Palette::Light, false), expected);
+    }
+
+    #[test]
+    fn light_band() {
+        let input = concat!(
+            style!(reset to bg = color!(black)),
+            " H ",
+            style!(fg = color!(black), bg = color!(blue), symbol!(div)),
+            style!(fg = color!(black)),
+            " ~ ",
+        );
+        let expected = concat!(
+            style!(reset to bg = color!([254])),
+            " H ",
+            style!(fg = color!([254]), bg = color!([74]), symbol!(div)),
+            style!(fg = color!(black)),
+            " ~ ",
+        );
+        assert_eq!(theme(&[input], Palette::Light, false), expected);
+    }
+
+    #[test]
+    fn split_sequence() {
+        assert_eq!(
+            theme(&["a\x1b", "[3", "4;4", "0mb"], Palette::Solarized, false),
+            "a\x1b[38;2;38;139;210;48;2;7;54;66mb"
+        );
+    }
+
+    #[test]
+    fn untouched() {
+        let input = "\x1b[1;38;2;1;2;3m\x1b]0;title\x07\x1b[2Kabc";
+        assert_eq!(theme(&[input], Palette::HighContrast, false), input);
+    }
+
+    #[test]
+    fn tmux() {
+        let input = "#[fg=colour246,bg=colour237] 1 zsh#[none]## #[fg=blue,bg=default]x";
+        let expected = "#[fg=colour240,bg=colour252] 1 zsh#[none]## #[fg=colour74,bg=default]x";
+        assert_eq!(theme(&[input], Palette::Light, true), expected);
+    }
+}
@@ -37,7 +37,12 @@ where
         for (i, byte) in buf.iter().copied().enumerate() {
             match self.escape {
                 Escape::Normal => {
-                    if byte == b'' {
+                    // A bare `%` would be taken as a prompt escape
+                    if byte == b'%' {
+                        self.out.write_all(&buf[cursor..i])?;
+                        self.out.write_all(b"%%")?;
+                        cursor = i + 1;
+                    } else if byte == b'' {
                         if cursor < i {
                             self.out.write_all(&buf[cursor..i])?;
                             cursor = i;
@@ -144,4 +149,14 @@ mod tests {
         let output = String::from_utf8(output).unwrap();
         assert_eq!(output, expected);
     }
+
+    #[test]
+    fn percent() {
+        let input = String::from("64% [31m100%[m");
+        let expected = String::from("64%% %{[31m%}100%%%{[m%}");
+        let mut output = Vec::new();
+        Zsh::new(&mut output).write_all(input.as_bytes()).unwrap();
+        let output = String::from_utf8(output).unwrap();
+        assert_eq!(output, expected);
+    }
 }
@@ -0,0 +1,55 @@
+use simd_json::prelude::ValueAsScalar;
+
+pub fn context() -> Option<String> {
+    let var = |name| std::env::var(name).ok().filter(|value| !value.is_empty());
+
+    let context = if let Some(context) = var("DOCKER_CONTEXT") {
+        context
+    } else if let Some(host) = var("DOCKER_HOST") {
+        host
+    } else {
+        let dir = std::env::var_os("DOCKER_CONFIG")
+            .map(std::path::PathBuf::from)
+            .or_else(|| {
+                std::env::var_os("HOME").map(|home| std::path::PathBuf::from(home).join(".docker"))
+            })?;
+
+        let mut config = std::fs::read(dir.join("config.json")).ok()?;
+        current_context(&mut config)?
+    };
+
+    (context != "default").then_some(context)
+}
+
+fn current_context(config: &mut [u8]) -> Option<String> {
+    let tape = simd_json::to_tape(config).ok()?;
+    tape.as_value()
+        .get("currentContext")?
+        .as_str()
+        .filter(|context| !context.is_empty())
+        .map(String::from)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn config() {
+        let mut config =
+            Vec::from(&b"{\"auths\": {}, \"currentContext\": \"colima\", \"plugins\": {}}"[..]);
+        assert_eq!(current_context(&mut config), Some(String::from("colima")));
+    }
+
+    #[test]
+    fn no_context() {
+        let mut config = Vec::from(&b"{\"auths\": {}}"[..]);
+        assert_eq!(current_context(&mut config), None);
+
+        let mut config = Vec::from(&b"{\"currentContext\": \"\"}"[..]);
+        assert_eq!(current_context(&mut config), None);
+
+        let mut config = Vec::from(&b"not json"[..]);
+        assert_eq!(current_context(&mut config), None);
+    }
+}
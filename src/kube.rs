@@ -0,0 +1,239 @@
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Context {
+    pub name: String,
+    pub namespace: Option<String>,
+    pub production: bool,
+}
+
+pub fn current() -> Option<Context> {
+    let paths = match std::env::var_os("KUBECONFIG").filter(|paths| !paths.is_empty()) {
+        Some(paths) => std::env::split_paths(&paths).collect(),
+        None => vec![
+            std::path::PathBuf::from(std::env::var_os("HOME")?)
+                .join(".kube")
+                .join("config"),
+        ],
+    };
+
+    let configs = paths
+        .iter()
+        .filter_map(|path| std::fs::read_to_string(path).ok())
+        .collect::<Vec<_>>();
+
+    let patterns = std::env::var("SIMPALT_KUBE_PRODUCTION").unwrap_or_default();
+    resolve(configs.iter().map(String::as_str), &patterns)
+}
+
+fn resolve<'a, I>(configs: I, patterns: &str) -> Option<Context>
+where
+    I: Iterator<Item = &'a str> + Clone,
+{
+    let name = configs.clone().find_map(current_context)?;
+    let namespace = configs
+        .filter_map(|config| {
+            contexts(config)
+                .into_iter()
+                .find(|(context, _)| *context == name)
+        })
+        .map(|(_, namespace)| namespace)
+        .next()
+        .flatten();
+
+    Some(Context {
+        production: patterns
+            .split(',')
+            .map(str::trim)
+            .filter(|pattern| !pattern.is_empty())
+            .any(|pattern| glob(pattern, name)),
+        name: String::from(name),
+        namespace: namespace.map(String::from),
+    })
+}
+
+fn current_context(config: &str) -> Option<&str> {
+    config
+        .lines()
+        .find_map(|line| line.strip_prefix("current-context:"))
+        .map(unquote)
+        .filter(|name| !name.is_empty())
+}
+
+fn contexts(config: &str) -> Vec<(&str, Option<&str>)> {
+    let mut contexts = Vec::new();
+    let mut in_contexts = false;
+    let mut key_indent = 0;
+    let mut current: Option<(Option<&str>, Option<&str>)> = None;
+
+    for line in config.lines() {
+        let item = line.trim();
+        if item.is_empty() || item.starts_with('#') {
+            continue;
+        }
+
+        if !line.starts_with([' ', '-']) {
+            in_contexts = item == "contexts:";
+            continue;
+        }
+
+        if !in_contexts {
+            continue;
+        }
+
+        let mut indent = line.len() - line.trim_start().len();
+        let mut item = item;
+        if let Some(rest) = item.strip_prefix('-') {
+            if let Some((Some(name), namespace)) = current.take() {
+                contexts.push((name, namespace));
+            }
+            current = Some((None, None));
+            indent += 1 + rest.len() - rest.trim_start().len();
+            key_indent = indent;
+            item = rest.trim_start();
+        }
+
+        let Some((name, namespace)) = current.as_mut() else {
+            continue;
+        };
+
+        if indent == key_indent
+            && let Some(value) = item.strip_prefix("name:")
+        {
+            *name = Some(unquote(value));
+        } else if indent > key_indent
+            && let Some(value) = item.strip_prefix("namespace:")
+        {
+            *namespace = Some(unquote(value)).filter(|namespace| !namespace.is_empty());
+        }
+    }
+
+    if let Some((Some(name), namespace)) = current {
+        contexts.push((name, namespace));
+    }
+
+    contexts
+}
+
+fn unquote(value: &str) -> &str {
+    let value = value.trim();
+    value
+        .strip_prefix('"')
+        .and_then(|value| value.strip_suffix('"'))
+        .or_else(|| {
+            value
+                .strip_prefix('\'')
+                .and_then(|value| value.strip_suffix('\''))
+        })
+        .unwrap_or(value)
+}
+
+fn glob(pattern: &str, text: &str) -> bool {
+    match pattern.split_once('*') {
+        None => pattern == text,
+        Some((prefix, rest)) => text.strip_prefix(prefix).is_some_and(|text| {
+            rest.is_empty()
+                || text
+                    .char_indices()
+                    .map(|(i, _)| i)
+                    .chain(std::iter::once(text.len()))
+                    .any(|i| glob(rest, &text[i..]))
+        }),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CONFIG: &str = r#"apiVersion: v1
+clusters:
+- cluster:
+    server: https://127.0.0.1:6443
+  name: local
+contexts:
+- context:
+    cluster: local
+    user: admin
+  name: local
+- name: "prod-eu"
+  context:
+    cluster: prod-eu
+    namespace: payments
+    user: admin
+# a comment
+- context:
+    cluster: staging
+    namespace: ''
+    user: admin
+  name: staging
+current-context: prod-eu
+kind: Config
+users:
+- name: admin
+  user:
+    token: secret
+"#;
+
+    #[test]
+    fn current() {
+        assert_eq!(current_context(CONFIG), Some("prod-eu"));
+        assert_eq!(current_context("current-context: ''\n"), None);
+        assert_eq!(current_context("kind: Config\n"), None);
+    }
+
+    #[test]
+    fn list_contexts() {
+        assert_eq!(
+            contexts(CONFIG),
+            vec![
+                ("local", None),
+                ("prod-eu", Some("payments")),
+                ("staging", None)
+            ]
+        );
+    }
+
+    #[test]
+    fn indented_list() {
+        let config =
+            "contexts:\n  - context:\n      namespace: ns\n    name: ctx\ncurrent-context: ctx\n";
+        assert_eq!(contexts(config), vec![("ctx", Some("ns"))]);
+    }
+
+    #[test]
+    fn merged_configs() {
+        let first = "contexts:\n- context:\n    namespace: first\n  name: other\n";
+        assert_eq!(
+            resolve([first, CONFIG].into_iter(), ""),
+            Some(Context {
+                name: String::from("prod-eu"),
+                namespace: Some(String::from("payments")),
+                production: false,
+            })
+        );
+        assert_eq!(resolve([first].into_iter(), ""), None);
+    }
+
+    #[test]
+    fn production() {
+        assert!(
+            resolve([CONFIG].into_iter(), "dev, prod-*")
+                .unwrap()
+                .production
+        );
+        assert!(!resolve([CONFIG].into_iter(), "prod").unwrap().production);
+        assert!(!resolve([CONFIG].into_iter(), " , ").unwrap().production);
+    }
+
+    #[test]
+    fn glob_patterns() {
+        assert!(glob("prod", "prod"));
+        assert!(!glob("prod", "production"));
+        assert!(glob("prod*", "production"));
+        assert!(glob("*prod*", "eu-prod-1"));
+        assert!(glob("*-prod", "eu-prod"));
+        assert!(!glob("*-prod", "eu-prod-1"));
+        assert!(glob("a*b*c", "aXXbYYc"));
+        assert!(glob("*", ""));
+        assert!(glob("ü*", "über"));
+    }
+}
@@ -72,9 +72,69 @@ macro_rules! symbol {
     (direnv) => {
         ""
     };
+    (nix) => {
+        ""
+    };
     (python) => {
         "󰌠"
     };
+    (root) => {
+        ""
+    };
+    (container) => {
+        ""
+    };
+    (conda) => {
+        "󱔎"
+    };
+    (lock) => {
+        ""
+    };
+    (kube) => {
+        "󱃾"
+    };
+    (docker) => {
+        "󰡨"
+    };
+    (aws) => {
+        ""
+    };
+    (gcp) => {
+        "󱇶"
+    };
+    (azure) => {
+        "󰠅"
+    };
+    (session) => {
+        ""
+    };
+    (host) => {
+        "󰒋"
+    };
+    (load) => {
+        "󰓅"
+    };
+    (memory) => {
+        "󰍛"
+    };
+    (battery) => {
+        "󰁹"
+    };
+    (battery low) => {
+        "󰂃"
+    };
+    (terraform) => {
+        "󱁢"
+    };
+    (rust) => {
+        ""
+    };
+    (node) => {
+        ""
+    };
+    (go) => {
+        ""
+    };
     (new) => {
         ""
     };
@@ -126,15 +186,26 @@ macro_rules! symbol {
     (slant) => {
         ""
     };
+    (slant end) => {
+        ""
+    };
     (slant thin) => {
         ""
     };
 }
 
 mod args;
+mod battery;
+mod cloud;
 mod command;
 mod compat;
+mod docker;
 mod git;
+mod kube;
+mod palette;
+mod symbols;
+mod system;
+mod toolchain;
 
 type Result<T = ()> = std::io::Result<T>;
 
@@ -151,3 +222,88 @@ where
     unsafe { testing(buffer.as_mut_vec()).unwrap() };
     buffer
 }
+
+// Temporary directory populated with `files`, where paths ending in `/` are created as directories
+#[cfg(test)]
+struct Fixture(std::path::PathBuf);
+
+#[cfg(test)]
+impl Fixture {
+    fn new(name: &str, files: &[(&str, &str)]) -> Self {
+        let root =
+            std::env::temp_dir().join(format!("simpalt-{name}-{pid}", pid = std::process::id()));
+        drop(std::fs::remove_dir_all(&root));
+        std::fs::create_dir_all(&root).unwrap();
+        for (path, content) in files {
+            if path.ends_with('/') {
+                std::fs::create_dir_all(root.join(path)).unwrap();
+            } else {
+                let path = root.join(path);
+                std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+                std::fs::write(path, content).unwrap();
+            }
+        }
+        Self(root)
+    }
+}
+
+#[cfg(test)]
+impl Drop for Fixture {
+    fn drop(&mut self) {
+        drop(std::fs::remove_dir_all(&self.0));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn glyphs() {
+        for glyph in [
+            symbol!(error),
+            symbol!(jobs),
+            symbol!(direnv),
+            symbol!(nix),
+            symbol!(python),
+            symbol!(root),
+            symbol!(container),
+            symbol!(conda),
+            symbol!(lock),
+            symbol!(kube),
+            symbol!(docker),
+            symbol!(aws),
+            symbol!(gcp),
+            symbol!(azure),
+            symbol!(session),
+            symbol!(host),
+            symbol!(load),
+            symbol!(memory),
+            symbol!(battery),
+            symbol!(battery low),
+            symbol!(terraform),
+            symbol!(rust),
+            symbol!(node),
+            symbol!(go),
+            symbol!(new),
+            symbol!(branch),
+            symbol!(ref),
+            symbol!(merge),
+            symbol!(bisect),
+            symbol!(rebase),
+            symbol!(cherry),
+            symbol!(revert),
+            symbol!(mailbox),
+            symbol!(ahead),
+            symbol!(behind),
+            symbol!(local),
+            symbol!(gone),
+            symbol!(warn),
+            symbol!(div),
+            symbol!(div thin),
+            symbol!(slant),
+            symbol!(slant end),
+            symbol!(slant thin),
+        ] {
+            assert_eq!(glyph.chars().count(), 1, "{glyph:?}");
+        }
+    }
+}
@@ -0,0 +1,422 @@
+const NAMES: [&str; 8] = [
+    "black", "red", "green", "yellow", "blue", "magenta", "cyan", "white",
+];
+
+const LIGHT: &[(Color, Color)] = &[
+    (Color::Base(1), Color::Indexed(167)),
+    (Color::Base(2), Color::Indexed(71)),
+    (Color::Base(3), Color::Indexed(178)),
+    (Color::Base(4), Color::Indexed(74)),
+    (Color::Base(5), Color::Indexed(133)),
+    (Color::Base(6), Color::Indexed(73)),
+    (Color::Base(7), Color::Indexed(240)),
+    (Color::Indexed(23), Color::Indexed(31)),
+    (Color::Indexed(236), Color::Indexed(253)),
+    (Color::Indexed(237), Color::Indexed(252)),
+    (Color::Indexed(238), Color::Indexed(250)),
+    (Color::Indexed(239), Color::Indexed(249)),
+    (Color::Indexed(246), Color::Indexed(240)),
+];
+
+// Black is both the text on the colored segments and the band behind the host, so only the band
+// is lightened
+const LIGHT_BACKGROUND: &[(Color, Color)] = &[(Color::Base(0), Color::Indexed(254))];
+
+const SOLARIZED: &[(Color, Color)] = &[
+    (Color::Base(0), Color::Rgb(0x07, 0x36, 0x42)),
+    (Color::Base(1), Color::Rgb(0xdc, 0x32, 0x2f)),
+    (Color::Base(2), Color::Rgb(0x85, 0x99, 0x00)),
+    (Color::Base(3), Color::Rgb(0xb5, 0x89, 0x00)),
+    (Color::Base(4), Color::Rgb(0x26, 0x8b, 0xd2)),
+    (Color::Base(5), Color::Rgb(0xd3, 0x36, 0x82)),
+    (Color::Base(6), Color::Rgb(0x2a, 0xa1, 0x98)),
+    (Color::Base(7), Color::Rgb(0xee, 0xe8, 0xd5)),
+    (Color::Indexed(23), Color::Rgb(0x2a, 0xa1, 0x98)),
+    (Color::Indexed(236), Color::Rgb(0x00, 0x2b, 0x36)),
+    (Color::Indexed(237), Color::Rgb(0x07, 0x36, 0x42)),
+    (Color::Indexed(238), Color::Rgb(0x07, 0x36, 0x42)),
+    (Color::Indexed(239), Color::Rgb(0x58, 0x6e, 0x75)),
+    (Color::Indexed(246), Color::Rgb(0x93, 0xa1, 0xa1)),
+];
+
+const HIGH_CONTRAST: &[(Color, Color)] = &[
+    (Color::Base(0), Color::Indexed(16)),
+    (Color::Base(1), Color::Indexed(196)),
+    (Color::Base(2), Color::Indexed(46)),
+    (Color::Base(3), Color::Indexed(226)),
+    (Color::Base(4), Color::Indexed(39)),
+    (Color::Base(5), Color::Indexed(201)),
+    (Color::Base(6), Color::Indexed(51)),
+    (Color::Base(7), Color::Indexed(231)),
+    (Color::Indexed(23), Color::Indexed(51)),
+    (Color::Indexed(236), Color::Indexed(16)),
+    (Color::Indexed(237), Color::Indexed(234)),
+    (Color::Indexed(238), Color::Indexed(235)),
+    (Color::Indexed(239), Color::Indexed(236)),
+    (Color::Indexed(246), Color::Indexed(255)),
+];
+
+// The xterm defaults for the 16 system colors
+const SYSTEM: [(u8, u8, u8); 16] = [
+    (0, 0, 0),
+    (205, 0, 0),
+    (0, 205, 0),
+    (205, 205, 0),
+    (0, 0, 238),
+    (205, 0, 205),
+    (0, 205, 205),
+    (229, 229, 229),
+    (127, 127, 127),
+    (255, 0, 0),
+    (0, 255, 0),
+    (255, 255, 0),
+    (92, 92, 255),
+    (255, 0, 255),
+    (0, 255, 255),
+    (255, 255, 255),
+];
+
+const CUBE: [u8; 6] = [0, 95, 135, 175, 215, 255];
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum Layer {
+    Foreground,
+    Background,
+}
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum Color {
+    Base(u8),
+    Indexed(u8),
+    Rgb(u8, u8, u8),
+    Reset,
+}
+
+impl Color {
+    pub fn parse_tmux(color: &str) -> Option<Self> {
+        if color == "default" {
+            return Some(Self::Reset);
+        }
+
+        if let Some(base) = NAMES.iter().position(|name| *name == color) {
+            return u8::try_from(base).ok().map(Self::Base);
+        }
+
+        if let Some(index) = color
+            .strip_prefix("colour")
+            .or_else(|| color.strip_prefix("color"))
+        {
+            return index.parse().ok().map(Self::Indexed);
+        }
+
+        let hex = color.strip_prefix('#').filter(|hex| hex.len() == 6)?;
+        let channel = |i| u8::from_str_radix(hex.get(i..i + 2)?, 16).ok();
+        Some(Self::Rgb(channel(0)?, channel(2)?, channel(4)?))
+    }
+
+    // Without the leading `3` or `4` selecting foreground or background
+    pub fn sgr(self) -> String {
+        match self {
+            Self::Base(base) => format!("{base}"),
+            Self::Indexed(index) => format!("8;5;{index}"),
+            Self::Rgb(r, g, b) => format!("8;2;{r};{g};{b}"),
+            Self::Reset => String::from("9"),
+        }
+    }
+
+    pub fn downgrade(self, depth: Depth) -> Self {
+        match (depth, self) {
+            (Depth::Indexed, Self::Rgb(r, g, b)) => Self::Indexed(nearest_indexed(r, g, b)),
+            (Depth::Base, Self::Indexed(index)) if index < 16 => Self::Base(index & 7),
+            (Depth::Base, Self::Indexed(_) | Self::Rgb(..)) => Self::Base(nearest_base(self.rgb())),
+            _ => self,
+        }
+    }
+
+    fn rgb(self) -> (u8, u8, u8) {
+        match self {
+            Self::Base(base) => SYSTEM[usize::from(base & 7)],
+            Self::Indexed(index @ 0..16) => SYSTEM[usize::from(index)],
+            Self::Indexed(index @ 16..232) => {
+                let index = usize::from(index - 16);
+                (CUBE[index / 36], CUBE[index / 6 % 6], CUBE[index % 6])
+            }
+            Self::Indexed(index) => {
+                let level = 8 + (index - 232) * 10;
+                (level, level, level)
+            }
+            Self::Rgb(r, g, b) => (r, g, b),
+            Self::Reset => (0, 0, 0),
+        }
+    }
+
+    pub fn tmux(self) -> String {
+        match self {
+            Self::Base(base) => String::from(NAMES[usize::from(base & 7)]),
+            Self::Indexed(index) => format!("colour{index}"),
+            Self::Rgb(r, g, b) => format!("#{r:02x}{g:02x}{b:02x}"),
+            Self::Reset => String::from("default"),
+        }
+    }
+}
+
+fn nearest_indexed(r: u8, g: u8, b: u8) -> u8 {
+    let level = |channel: u8| {
+        (0..6)
+            .min_by_key(|&level| CUBE[usize::from(level)].abs_diff(channel))
+            .unwrap_or(0)
+    };
+    let cube = 16 + level(r) * 36 + level(g) * 6 + level(b);
+
+    let average = (u16::from(r) + u16::from(g) + u16::from(b)) / 3;
+    let step = u8::try_from(average.saturating_sub(3) / 10).unwrap_or(u8::MAX);
+    let gray = 232 + step.min(23);
+
+    [cube, gray]
+        .into_iter()
+        .min_by_key(|&index| distance(Color::Indexed(index).rgb(), (r, g, b)))
+        .unwrap_or(cube)
+}
+
+// Picks by lightness first, so dark shades stay black and grays split into black or white,
+// and only then by hue, as the 8 colors are rendered differently by every terminal
+fn nearest_base((r, g, b): (u8, u8, u8)) -> u8 {
+    let max = r.max(g).max(b);
+    let min = r.min(g).min(b);
+    let chroma = max - min;
+
+    if max < 80 {
+        return 0;
+    }
+
+    if chroma < 48 {
+        return if u16::from(max) + u16::from(min) < 256 {
+            0
+        } else {
+            7
+        };
+    }
+
+    let delta = |from: u8, to: u8| 60.0 * (f32::from(from) - f32::from(to)) / f32::from(chroma);
+    let hue = if max == r {
+        delta(g, b).rem_euclid(360.0)
+    } else if max == g {
+        120.0 + delta(b, r)
+    } else {
+        240.0 + delta(r, g)
+    };
+
+    match hue {
+        30.0..65.0 => 3,
+        65.0..150.0 => 2,
+        150.0..195.0 => 6,
+        195.0..270.0 => 4,
+        270.0..345.0 => 5,
+        _ => 1,
+    }
+}
+
+fn distance(from: (u8, u8, u8), to: (u8, u8, u8)) -> u32 {
+    let channel = |from: u8, to: u8| u32::from(from.abs_diff(to)).pow(2);
+    channel(from.0, to.0) + channel(from.1, to.1) + channel(from.2, to.2)
+}
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum Depth {
+    True,
+    Indexed,
+    Base,
+}
+
+impl Depth {
+    pub fn from_env() -> Self {
+        Self::from_vars(|var| std::env::var(var).ok())
+    }
+
+    // Only terminals known to lack 256 colors drop to the 8 basic ones, as most others support
+    // 256 colors even without saying so in their `TERM`
+    fn from_vars<F>(var: F) -> Self
+    where
+        F: Fn(&str) -> Option<String>,
+    {
+        let var = |name| var(name).filter(|value| !value.is_empty());
+
+        if matches!(var("COLORTERM").as_deref(), Some("truecolor" | "24bit")) {
+            return Self::True;
+        }
+
+        match var("TERM").as_deref() {
+            None => Self::True,
+            Some(term) if term.contains("256color") => Self::Indexed,
+            Some(
+                "linux" | "vt100" | "vt102" | "vt220" | "ansi" | "cons25" | "rxvt" | "xterm-color"
+                | "eterm-color",
+            ) => Self::Base,
+            Some(term) if term.ends_with("-16color") || term.ends_with("-8color") => Self::Base,
+            Some(_) => Self::Indexed,
+        }
+    }
+}
+
+#[derive(Debug, Default, Copy, Clone, Eq, PartialEq)]
+pub enum Palette {
+    #[default]
+    Dark,
+    Light,
+    Solarized,
+    HighContrast,
+}
+
+impl Palette {
+    pub fn from_env() -> Self {
+        match std::env::var("SIMPALT_THEME").as_deref() {
+            Ok("light") => Self::Light,
+            Ok("solarized") => Self::Solarized,
+            Ok("high-contrast") => Self::HighContrast,
+            _ => Self::Dark,
+        }
+    }
+
+    pub fn get(self, layer: Layer, color: Color) -> Color {
+        let (table, background) = match self {
+            Self::Dark => return color,
+            Self::Light => (LIGHT, LIGHT_BACKGROUND),
+            Self::Solarized => (SOLARIZED, &[][..]),
+            Self::HighContrast => (HIGH_CONTRAST, &[][..]),
+        };
+
+        let overrides = match layer {
+            Layer::Foreground => &[][..],
+            Layer::Background => background,
+        };
+
+        overrides
+            .iter()
+            .chain(table)
+            .find(|(from, _)| *from == color)
+            .map_or(color, |&(_, to)| to)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_tmux() {
+        assert_eq!(Color::parse_tmux("default"), Some(Color::Reset));
+        assert_eq!(Color::parse_tmux("blue"), Some(Color::Base(4)));
+        assert_eq!(Color::parse_tmux("colour236"), Some(Color::Indexed(236)));
+        assert_eq!(Color::parse_tmux("color7"), Some(Color::Indexed(7)));
+        assert_eq!(
+            Color::parse_tmux("#0a80ff"),
+            Some(Color::Rgb(0x0a, 0x80, 0xff))
+        );
+        assert_eq!(Color::parse_tmux("#0a80f"), None);
+        assert_eq!(Color::parse_tmux("brightred"), None);
+    }
+
+    #[test]
+    fn format() {
+        assert_eq!(Color::Base(4).sgr(), "4");
+        assert_eq!(Color::Indexed(236).sgr(), "8;5;236");
+        assert_eq!(Color::Rgb(1, 2, 3).sgr(), "8;2;1;2;3");
+        assert_eq!(Color::Reset.sgr(), "9");
+        assert_eq!(Color::Base(4).tmux(), "blue");
+        assert_eq!(Color::Rgb(1, 2, 255).tmux(), "#0102ff");
+    }
+
+    #[test]
+    fn get() {
+        let fg = Layer::Foreground;
+        let bg = Layer::Background;
+        assert_eq!(Palette::Dark.get(bg, Color::Base(0)), Color::Base(0));
+        assert_eq!(Palette::Light.get(fg, Color::Base(4)), Color::Indexed(74));
+        assert_eq!(Palette::Light.get(bg, Color::Base(4)), Color::Indexed(74));
+        assert_eq!(Palette::Light.get(fg, Color::Base(0)), Color::Base(0));
+        assert_eq!(Palette::Light.get(bg, Color::Base(0)), Color::Indexed(254));
+        assert_eq!(
+            Palette::Solarized.get(fg, Color::Indexed(246)),
+            Color::Rgb(0x93, 0xa1, 0xa1)
+        );
+        assert_eq!(Palette::HighContrast.get(bg, Color::Reset), Color::Reset);
+    }
+
+    #[test]
+    fn downgrade() {
+        let solarized = Color::Rgb(0x26, 0x8b, 0xd2);
+        assert_eq!(solarized.downgrade(Depth::True), solarized);
+        assert_eq!(solarized.downgrade(Depth::Indexed), Color::Indexed(32));
+        assert_eq!(solarized.downgrade(Depth::Base), Color::Base(4));
+        assert_eq!(
+            Color::Rgb(0x07, 0x36, 0x42).downgrade(Depth::Indexed),
+            Color::Indexed(235)
+        );
+        assert_eq!(
+            Color::Indexed(74).downgrade(Depth::Indexed),
+            Color::Indexed(74)
+        );
+        assert_eq!(Color::Indexed(74).downgrade(Depth::Base), Color::Base(4));
+        assert_eq!(Color::Indexed(246).downgrade(Depth::Base), Color::Base(7));
+        assert_eq!(Color::Indexed(236).downgrade(Depth::Base), Color::Base(0));
+        assert_eq!(Color::Indexed(23).downgrade(Depth::Base), Color::Base(6));
+        assert_eq!(Color::Indexed(9).downgrade(Depth::Base), Color::Base(1));
+        assert_eq!(Color::Base(3).downgrade(Depth::Base), Color::Base(3));
+        assert_eq!(Color::Reset.downgrade(Depth::Base), Color::Reset);
+    }
+
+    #[test]
+    fn depth() {
+        let depth = |vars: &[(&str, &str)]| {
+            Depth::from_vars(|name| {
+                vars.iter()
+                    .find(|(var, _)| *var == name)
+                    .map(|(_, value)| String::from(*value))
+            })
+        };
+        assert_eq!(depth(&[]), Depth::True);
+        assert_eq!(
+            depth(&[("COLORTERM", "truecolor"), ("TERM", "linux")]),
+            Depth::True
+        );
+        assert_eq!(depth(&[("TERM", "xterm-256color")]), Depth::Indexed);
+        assert_eq!(depth(&[("TERM", "linux")]), Depth::Base);
+        assert_eq!(depth(&[("TERM", "xterm-16color")]), Depth::Base);
+
+        // Unknown terminals keep their 256 colors, such as the clock on the right
+        let unknown = depth(&[("TERM", "xterm-kitty")]);
+        assert_eq!(unknown, Depth::Indexed);
+        assert_eq!(Color::Indexed(23).downgrade(unknown), Color::Indexed(23));
+    }
+
+    #[test]
+    fn base_lightness() {
+        assert_eq!(
+            Color::Rgb(0x00, 0x2b, 0x36).downgrade(Depth::Base),
+            Color::Base(0)
+        );
+        assert_eq!(
+            Color::Rgb(0x07, 0x36, 0x42).downgrade(Depth::Base),
+            Color::Base(0)
+        );
+        assert_eq!(
+            Color::Rgb(0x93, 0xa1, 0xa1).downgrade(Depth::Base),
+            Color::Base(7)
+        );
+
+        // Every solarized color lands back on the base color it themes
+        for &(base, color) in SOLARIZED {
+            if let Color::Base(_) = base {
+                assert_eq!(color.downgrade(Depth::Base), base);
+            }
+        }
+    }
+
+    #[test]
+    fn indexed() {
+        assert_eq!(nearest_indexed(0, 0, 0), 16);
+        assert_eq!(nearest_indexed(255, 255, 255), 231);
+        assert_eq!(nearest_indexed(0x80, 0x80, 0x80), 244);
+        assert_eq!(nearest_indexed(0xd7, 0x5f, 0x87), 168);
+    }
+}
@@ -0,0 +1,138 @@
+// (Nerd Font, Unicode, ASCII)
+const TABLE: &[(&str, &str, &str)] = &[
+    (symbol!(error), "✘", "x"),
+    (symbol!(jobs), "⚙", "&"),
+    (symbol!(direnv), "≡", "env"),
+    (symbol!(nix), "❄", "nix"),
+    (symbol!(python), "π", "py"),
+    (symbol!(root), "#", "#"),
+    (symbol!(container), "⬡", "ct"),
+    (symbol!(conda), "◎", "conda"),
+    (symbol!(lock), "⊘", "ro"),
+    (symbol!(kube), "⎈", "k8s"),
+    (symbol!(docker), "▣", "docker"),
+    (symbol!(aws), "☁", "aws"),
+    (symbol!(gcp), "◈", "gcp"),
+    (symbol!(azure), "△", "az"),
+    (symbol!(session), "❐", "$"),
+    (symbol!(host), "⌂", "@"),
+    (symbol!(load), "↯", "load"),
+    (symbol!(memory), "▤", "mem"),
+    (symbol!(battery), "▮", "bat"),
+    (symbol!(battery low), "▯", "BAT"),
+    (symbol!(terraform), "◆", "tf"),
+    (symbol!(rust), "®", "rs"),
+    (symbol!(node), "⬢", "js"),
+    (symbol!(go), "γ", "go"),
+    (symbol!(new), "✚", "+"),
+    (symbol!(branch), "⎇", "*"),
+    (symbol!(ref), "➦", "->"),
+    (symbol!(merge), "⑃", "merge"),
+    (symbol!(bisect), "½", "bisect"),
+    (symbol!(rebase), "↻", "rebase"),
+    (symbol!(cherry), "✿", "pick"),
+    (symbol!(revert), "↺", "revert"),
+    (symbol!(mailbox), "✉", "am"),
+    (symbol!(ahead), "↑", "^"),
+    (symbol!(behind), "↓", "v"),
+    (symbol!(local), "◇", "L"),
+    (symbol!(gone), "✗", "-"),
+    (symbol!(warn), "⚠", "!"),
+    (symbol!(div), "▌", ">"),
+    (symbol!(div thin), "│", "|"),
+    (symbol!(slant), "◥", "/"),
+    (symbol!(slant end), "◣", "|"),
+    (symbol!(slant thin), "╲", "\\"),
+];
+
+#[derive(Debug, Default, Copy, Clone, Eq, PartialEq)]
+pub enum Set {
+    #[default]
+    Nerd,
+    Unicode,
+    Ascii,
+}
+
+impl Set {
+    pub fn from_env() -> Self {
+        match std::env::var("SIMPALT_SYMBOLS").as_deref() {
+            Ok("nerd") => Self::Nerd,
+            Ok("unicode") => Self::Unicode,
+            Ok("ascii") => Self::Ascii,
+            _ if std::env::var("TERM").is_ok_and(|term| term == "dumb") => Self::Ascii,
+            _ => Self::Nerd,
+        }
+    }
+
+    // Returns the glyph to draw in place of the Nerd Font `glyph`
+    pub fn get(self, glyph: &'static str) -> &'static str {
+        if self == Self::Nerd {
+            return glyph;
+        }
+
+        TABLE
+            .iter()
+            .find(|(nerd, _, _)| *nerd == glyph)
+            .map_or(glyph, |&(_, unicode, ascii)| match self {
+                Self::Unicode => unicode,
+                Self::Nerd | Self::Ascii => ascii,
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn get() {
+        assert_eq!(Set::Nerd.get(symbol!(branch)), symbol!(branch));
+        assert_eq!(Set::Unicode.get(symbol!(branch)), "⎇");
+        assert_eq!(Set::Ascii.get(symbol!(branch)), "*");
+        assert_eq!(Set::Ascii.get("é"), "é");
+    }
+
+    #[test]
+    fn no_private_use() {
+        for set in [Set::Unicode, Set::Ascii] {
+            for (nerd, _, _) in TABLE {
+                assert!(
+                    !set.get(nerd)
+                        .chars()
+                        .any(|c| ('\u{e000}'..='\u{f8ff}').contains(&c) || c >= '\u{f0000}')
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn visible_fallbacks() {
+        for set in [Set::Unicode, Set::Ascii] {
+            for (nerd, _, _) in TABLE {
+                assert!(!set.get(nerd).trim().is_empty(), "{nerd}");
+            }
+        }
+    }
+
+    #[test]
+    fn distinct_fallbacks() {
+        // Separators only frame the segments, so they may look like one another
+        let separators = [
+            symbol!(div),
+            symbol!(div thin),
+            symbol!(slant),
+            symbol!(slant end),
+            symbol!(slant thin),
+        ];
+
+        for set in [Set::Unicode, Set::Ascii] {
+            let mut seen = std::collections::HashSet::new();
+            for (nerd, _, _) in TABLE
+                .iter()
+                .filter(|(nerd, _, _)| !separators.contains(nerd))
+            {
+                assert!(seen.insert(set.get(nerd)), "{:?}", set.get(nerd));
+            }
+        }
+    }
+}
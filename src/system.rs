@@ -0,0 +1,115 @@
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct Load {
+    pub average: f32,
+    pub cpus: usize,
+}
+
+impl Load {
+    #[allow(clippy::cast_precision_loss)]
+    pub fn per_cpu(self) -> f32 {
+        self.average / self.cpus.max(1) as f32
+    }
+}
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub struct Memory {
+    pub total: u64,
+    pub available: u64,
+}
+
+impl Memory {
+    pub fn used_percent(self) -> u64 {
+        (self.total.saturating_sub(self.available) * 100)
+            .checked_div(self.total)
+            .unwrap_or(0)
+    }
+}
+
+pub fn load() -> Option<Load> {
+    let average = parse_loadavg(&std::fs::read_to_string("/proc/loadavg").ok()?)?;
+    let cpus = std::thread::available_parallelism().map_or(1, std::num::NonZeroUsize::get);
+    Some(Load { average, cpus })
+}
+
+pub fn memory() -> Option<Memory> {
+    parse_meminfo(&std::fs::read_to_string("/proc/meminfo").ok()?)
+}
+
+fn parse_loadavg(loadavg: &str) -> Option<f32> {
+    loadavg.split_whitespace().next()?.parse().ok()
+}
+
+fn parse_meminfo(meminfo: &str) -> Option<Memory> {
+    let field = |name| {
+        meminfo.lines().find_map(|line| {
+            line.strip_prefix(name)?
+                .strip_prefix(':')?
+                .trim()
+                .trim_end_matches("kB")
+                .trim()
+                .parse::<u64>()
+                .ok()
+        })
+    };
+
+    let total = field("MemTotal")?;
+    // Kernels before 3.14 do not report `MemAvailable`
+    let available = field("MemAvailable").or_else(|| {
+        Some(field("MemFree")? + field("Buffers").unwrap_or(0) + field("Cached").unwrap_or(0))
+    })?;
+
+    Some(Memory { total, available })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn loadavg() {
+        assert_eq!(parse_loadavg("0.52 0.58 0.59 1/467 12345\n"), Some(0.52));
+        assert_eq!(parse_loadavg(""), None);
+    }
+
+    #[test]
+    fn per_cpu() {
+        let load = Load {
+            average: 3.0,
+            cpus: 4,
+        };
+        assert!((load.per_cpu() - 0.75).abs() < f32::EPSILON);
+
+        let load = Load {
+            average: 1.5,
+            cpus: 0,
+        };
+        assert!((load.per_cpu() - 1.5).abs() < f32::EPSILON);
+    }
+
+    #[test]
+    fn meminfo() {
+        let meminfo = "MemTotal:       16000000 kB\nMemFree:         1000000 kB\nMemAvailable:    4000000 kB\nBuffers:          500000 kB\n";
+        let memory = parse_meminfo(meminfo).unwrap();
+        assert_eq!(
+            memory,
+            Memory {
+                total: 16_000_000,
+                available: 4_000_000,
+            }
+        );
+        assert_eq!(memory.used_percent(), 75);
+    }
+
+    #[test]
+    fn meminfo_without_available() {
+        let meminfo = "MemTotal: 1000 kB\nMemFree: 100 kB\nBuffers: 50 kB\nCached: 250 kB\n";
+        assert_eq!(
+            parse_meminfo(meminfo),
+            Some(Memory {
+                total: 1000,
+                available: 400,
+            })
+        );
+        assert_eq!(parse_meminfo("MemFree: 100 kB\n"), None);
+    }
+}
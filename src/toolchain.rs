@@ -0,0 +1,297 @@
+use simd_json::prelude::ValueAsScalar;
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum Tool {
+    Rust,
+    Node,
+    Go,
+    Python,
+    Other(String),
+}
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Toolchain {
+    pub tool: Tool,
+    pub version: String,
+}
+
+pub fn detect(pwd: &std::path::Path) -> Option<Toolchain> {
+    for dir in pwd.ancestors() {
+        if let Some(toolchain) = in_dir(dir) {
+            return Some(toolchain);
+        }
+
+        // Every other tool searches the parents for its pin, but a go module only uses its own
+        if dir.join("go.mod").is_file() {
+            return None;
+        }
+    }
+    None
+}
+
+fn in_dir(dir: &std::path::Path) -> Option<Toolchain> {
+    let read = |name| std::fs::read_to_string(dir.join(name)).ok();
+    let pinned = |tool, version: Option<&str>| {
+        version.map(|version| Toolchain {
+            tool,
+            version: String::from(version),
+        })
+    };
+
+    if let Some(toolchain) = read("rust-toolchain.toml") {
+        return pinned(Tool::Rust, toml_string(&toolchain, "channel"));
+    }
+
+    if let Some(toolchain) = read("rust-toolchain") {
+        return pinned(Tool::Rust, legacy_rust_toolchain(&toolchain));
+    }
+
+    if let Some(manifest) = read("Cargo.toml")
+        && let Some(toolchain) = pinned(Tool::Rust, toml_string(&manifest, "rust-version"))
+    {
+        return Some(toolchain);
+    }
+
+    if let Some(nvmrc) = read(".nvmrc") {
+        return pinned(Tool::Node, first_line(&nvmrc));
+    }
+
+    if let Some(package) = read("package.json")
+        && let Some(version) = package_node(package.into_bytes())
+    {
+        return pinned(Tool::Node, Some(&version));
+    }
+
+    if let Some(module) = read("go.mod")
+        && let Some(toolchain) = pinned(Tool::Go, go_version(&module))
+    {
+        return Some(toolchain);
+    }
+
+    if let Some(version) = read(".python-version") {
+        return pinned(Tool::Python, first_line(&version));
+    }
+
+    read(".tool-versions").and_then(|versions| tool_versions(&versions))
+}
+
+fn first_line(content: &str) -> Option<&str> {
+    content
+        .lines()
+        .map(str::trim)
+        .find(|line| !line.is_empty() && !line.starts_with('#'))
+}
+
+fn toml_string<'a>(toml: &'a str, key: &str) -> Option<&'a str> {
+    toml.lines().find_map(|line| {
+        let value = line
+            .trim()
+            .strip_prefix(key)?
+            .trim_start()
+            .strip_prefix('=')?
+            .trim();
+        value
+            .strip_prefix('"')
+            .and_then(|value| value.split_once('"'))
+            .or_else(|| {
+                value
+                    .strip_prefix('\'')
+                    .and_then(|value| value.split_once('\''))
+            })
+            .map(|(value, _)| value)
+            .filter(|value| !value.is_empty())
+    })
+}
+
+fn legacy_rust_toolchain(toolchain: &str) -> Option<&str> {
+    if toolchain.contains("[toolchain]") {
+        toml_string(toolchain, "channel")
+    } else {
+        first_line(toolchain)
+    }
+}
+
+fn package_node(mut package: Vec<u8>) -> Option<String> {
+    let tape = simd_json::to_tape(&mut package).ok()?;
+    let package = tape.as_value();
+
+    ["volta", "engines"].into_iter().find_map(|section| {
+        package
+            .get(section)?
+            .get("node")?
+            .as_str()
+            .map(String::from)
+    })
+}
+
+fn go_version(module: &str) -> Option<&str> {
+    let mut go = None;
+    for line in module.lines().map(str::trim) {
+        if let Some(toolchain) = line.strip_prefix("toolchain go") {
+            return Some(toolchain.trim());
+        }
+        if go.is_none() {
+            go = line
+                .strip_prefix("go ")
+                .map(str::trim)
+                .filter(|version| !version.is_empty());
+        }
+    }
+    go
+}
+
+fn tool_versions(versions: &str) -> Option<Toolchain> {
+    let mut entry = first_line(versions)?.split_whitespace();
+    let tool = entry.next()?;
+    let version = entry.next()?;
+
+    Some(Toolchain {
+        tool: match tool {
+            "rust" => Tool::Rust,
+            "nodejs" | "node" => Tool::Node,
+            "golang" | "go" => Tool::Go,
+            "python" => Tool::Python,
+            _ => Tool::Other(String::from(tool)),
+        },
+        version: String::from(version),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Fixture;
+
+    fn toolchain(tool: Tool, version: &str) -> Toolchain {
+        Toolchain {
+            tool,
+            version: String::from(version),
+        }
+    }
+
+    #[test]
+    fn walk_up() {
+        let fixture = Fixture::new(
+            "toolchain-walk",
+            &[
+                ("rust-toolchain.toml", "[toolchain]\nchannel = \"1.85.0\"\n"),
+                ("crates/core/Cargo.toml", "[package]\nname = \"core\"\n"),
+                ("web/.nvmrc", "v20.11.0\n"),
+            ],
+        );
+        assert_eq!(
+            detect(&fixture.0.join("crates").join("core")),
+            Some(toolchain(Tool::Rust, "1.85.0"))
+        );
+        assert_eq!(
+            detect(&fixture.0.join("web")),
+            Some(toolchain(Tool::Node, "v20.11.0"))
+        );
+    }
+
+    #[test]
+    fn stop_at_go_module() {
+        let fixture = Fixture::new(
+            "toolchain-stop",
+            &[
+                (".tool-versions", "nodejs 20.11.0\n"),
+                ("packages/app/package.json", "{\"name\": \"app\"}"),
+                ("services/api/go.mod", "module example.com/api\n"),
+            ],
+        );
+        assert_eq!(
+            detect(&fixture.0.join("packages").join("app")),
+            Some(toolchain(Tool::Node, "20.11.0"))
+        );
+        assert_eq!(detect(&fixture.0.join("services").join("api")), None);
+    }
+
+    #[test]
+    fn unpinned_project() {
+        let fixture = Fixture::new(
+            "toolchain-unpinned",
+            &[
+                ("Cargo.toml", "[package]\nname = \"simpalt\"\n"),
+                ("go.mod", "module example.com/m\n"),
+            ],
+        );
+        assert_eq!(in_dir(&fixture.0), None);
+    }
+
+    #[test]
+    fn cargo_rust_version() {
+        let fixture = Fixture::new(
+            "toolchain-cargo",
+            &[(
+                "Cargo.toml",
+                "[package]\nname = \"simpalt\"\nrust-version = \"1.80\" # msrv\n",
+            )],
+        );
+        assert_eq!(in_dir(&fixture.0), Some(toolchain(Tool::Rust, "1.80")));
+    }
+
+    #[test]
+    fn legacy_toolchain() {
+        assert_eq!(
+            legacy_rust_toolchain("nightly-2024-01-01\n"),
+            Some("nightly-2024-01-01")
+        );
+        assert_eq!(
+            legacy_rust_toolchain("[toolchain]\nchannel = 'stable'\n"),
+            Some("stable")
+        );
+    }
+
+    #[test]
+    fn toml() {
+        assert_eq!(
+            toml_string("rust-version = \"1.70\"", "rust-version"),
+            Some("1.70")
+        );
+        assert_eq!(
+            toml_string("rust-version.workspace = true", "rust-version"),
+            None
+        );
+        assert_eq!(toml_string("channel=''", "channel"), None);
+    }
+
+    #[test]
+    fn package_json() {
+        assert_eq!(
+            package_node(Vec::from(&b"{\"engines\": {\"node\": \">=18\"}}"[..])),
+            Some(String::from(">=18"))
+        );
+        assert_eq!(
+            package_node(Vec::from(
+                &b"{\"engines\": {\"node\": \">=18\"}, \"volta\": {\"node\": \"20.1.0\"}}"[..]
+            )),
+            Some(String::from("20.1.0"))
+        );
+        assert_eq!(package_node(Vec::from(&b"{\"name\": \"app\"}"[..])), None);
+    }
+
+    #[test]
+    fn go() {
+        assert_eq!(
+            go_version("module example.com/m\n\ngo 1.22\n\ntoolchain go1.22.3\n"),
+            Some("1.22.3")
+        );
+        assert_eq!(
+            go_version("module example.com/m\n\ngo 1.21\n"),
+            Some("1.21")
+        );
+    }
+
+    #[test]
+    fn asdf() {
+        assert_eq!(
+            tool_versions("# pinned\nnodejs 20.1.0\npython 3.12.0\n"),
+            Some(toolchain(Tool::Node, "20.1.0"))
+        );
+        assert_eq!(
+            tool_versions("terraform 1.7.0\n"),
+            Some(toolchain(Tool::Other(String::from("terraform")), "1.7.0"))
+        );
+        assert_eq!(tool_versions("ruby\n"), None);
+    }
+}